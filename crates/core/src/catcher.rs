@@ -32,6 +32,7 @@
 //! [`write_error_default`] to capture processing errors and send the default error page.
 
 use std::borrow::Cow;
+use std::fmt::{self, Formatter};
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -121,9 +122,28 @@ fn status_error_xml(code: StatusCode, name: &str, brief: &str, cause: Option<&st
         cause.unwrap_or(EMPTY_CAUSE_MSG)
     )
 }
+/// Problem details (RFC 7807), `title`, `status` and `detail` override the extension members with the same name.
+#[inline]
+fn status_error_problem_json(err: &StatusError) -> String {
+    let mut problem = err.extensions().clone();
+    problem.entry("type").or_insert_with(|| "about:blank".into());
+    problem.insert("title".into(), err.name.clone().into());
+    problem.insert("status".into(), err.code.as_u16().into());
+    problem.insert("detail".into(), err.brief.clone().into());
+    serde_json::Value::Object(problem).to_string()
+}
+#[inline]
+fn is_problem_json(format: &Mime) -> bool {
+    format.type_() == mime::APPLICATION && format.subtype() == "problem" && format.suffix() == Some(mime::JSON)
+}
 /// Create bytes from `StatusError`.
+///
+/// Problem details (RFC 7807) is rendered if `prefer_format` is `application/problem+json`.
 #[inline]
 pub fn status_error_bytes(err: &StatusError, prefer_format: &Mime, footer: Option<&str>) -> (Mime, Bytes) {
+    if is_problem_json(prefer_format) {
+        return (prefer_format.clone(), Bytes::from(status_error_problem_json(err)));
+    }
     let format = if !SUPPORTED_FORMATS.contains(&prefer_format.subtype()) {
         "text/html".parse().unwrap()
     } else {
@@ -152,6 +172,12 @@ pub struct Catcher {
     hoops: Vec<Arc<dyn Handler>>,
     handler: Arc<dyn Handler>,
 }
+impl fmt::Debug for Catcher {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Catcher").field("hoops", &self.hoops.len()).finish()
+    }
+}
 impl Default for Catcher {
     fn default() -> Self {
         Catcher {
@@ -332,4 +358,58 @@ mod tests {
 
         assert_eq!(access(&service, "notfound").await, "Custom 404 Error Page");
     }
+
+    #[tokio::test]
+    async fn test_problem_json() {
+        #[derive(serde::Deserialize, Extractible, Debug)]
+        #[extract(default_source(from = "query"))]
+        struct Paging {
+            page: u32,
+            size: u32,
+        }
+        #[handler]
+        async fn list(paging: Paging) -> String {
+            format!("{}-{}", paging.page, paging.size)
+        }
+        #[handler]
+        async fn gone(res: &mut Response) {
+            res.render(
+                StatusError::gone()
+                    .type_uri("https://example.com/probs/gone")
+                    .instance("/gone")
+                    .extension("balance", 30),
+            );
+        }
+        let router = Router::new()
+            .push(Router::with_path("list").get(list))
+            .push(Router::with_path("gone").get(gone));
+        let service = Service::new(router);
+
+        async fn access(service: &Service, name: &str) -> serde_json::Value {
+            let mut res = TestClient::get(format!("http://127.0.0.1:5800/{}", name))
+                .add_header("accept", "application/problem+json", true)
+                .send(service)
+                .await;
+            assert_eq!(res.headers().get("content-type").unwrap(), "application/problem+json");
+            res.take_json().await.unwrap()
+        }
+
+        let problem = access(&service, "gone").await;
+        assert_eq!(problem["type"], "https://example.com/probs/gone");
+        assert_eq!(problem["title"], "Gone");
+        assert_eq!(problem["status"], 410);
+        assert_eq!(problem["instance"], "/gone");
+        assert_eq!(problem["balance"], 30);
+
+        let problem = access(&service, "notfound").await;
+        assert_eq!(problem["type"], "about:blank");
+        assert_eq!(problem["status"], 404);
+
+        let problem = access(&service, "list?page=x&size=10").await;
+        assert_eq!(problem["status"], 400);
+        assert_eq!(problem["invalid-params"][0]["name"], "page");
+        let problem = access(&service, "list?page=1").await;
+        assert_eq!(problem["invalid-params"][0]["name"], "size");
+        assert_eq!(problem["invalid-params"][0]["reason"], "missing field `size`");
+    }
 }
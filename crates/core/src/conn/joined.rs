@@ -6,6 +6,7 @@ use std::task::{Context, Poll};
 
 use pin_project::pin_project;
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio_util::sync::CancellationToken;
 
 use crate::async_trait;
 use crate::conn::Holding;
@@ -123,10 +124,15 @@ where
             JoinedStream::B(b) => b.version().await,
         }
     }
-    async fn serve(self, handler: HyperHandler, builders: Arc<HttpBuilders>) -> IoResult<()> {
+    async fn serve(
+        self,
+        handler: HyperHandler,
+        builders: Arc<HttpBuilders>,
+        graceful_stop_token: CancellationToken,
+    ) -> IoResult<()> {
         match self {
-            JoinedStream::A(a) => a.serve(handler, builders).await,
-            JoinedStream::B(b) => b.serve(handler, builders).await,
+            JoinedStream::A(a) => a.serve(handler, builders, graceful_stop_token).await,
+            JoinedStream::B(b) => b.serve(handler, builders, graceful_stop_token).await,
         }
     }
 }
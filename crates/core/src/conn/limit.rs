@@ -0,0 +1,422 @@
+//! LimitListener and it's implements.
+use std::collections::HashMap;
+use std::io::Result as IoResult;
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use parking_lot::Mutex;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+use crate::async_trait;
+use crate::conn::{Holding, HttpBuilders};
+use crate::http::{HttpConnection, Version};
+use crate::service::HyperHandler;
+
+use super::{Accepted, Acceptor, Listener};
+
+/// What [`LimitListener`] does with the connections over the limit of connection count or accept rate.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Overflow {
+    /// Stop accepting until there is room for a new connection, incoming connections wait in the backlog of the
+    /// inner listener.
+    #[default]
+    Queue,
+    /// Accept the connections and close them at once.
+    Close,
+}
+
+#[derive(Default)]
+struct Counters {
+    alive: AtomicUsize,
+    rejected: AtomicU64,
+    per_ip: Mutex<HashMap<IpAddr, usize>>,
+}
+
+/// Connection counters of a [`LimitListener`], it can be cloned and read while the server is running.
+#[derive(Clone, Default)]
+pub struct LimitMetrics(Arc<Counters>);
+impl LimitMetrics {
+    /// Get the count of alive connections.
+    #[inline]
+    pub fn alive_connections(&self) -> usize {
+        self.0.alive.load(Ordering::Acquire)
+    }
+
+    /// Get the count of alive connections from the ip.
+    #[inline]
+    pub fn ip_connections(&self, ip: IpAddr) -> usize {
+        self.0.per_ip.lock().get(&ip).copied().unwrap_or_default()
+    }
+
+    /// Get the count of connections closed because of the limits.
+    #[inline]
+    pub fn rejected_connections(&self) -> u64 {
+        self.0.rejected.load(Ordering::Relaxed)
+    }
+}
+
+/// Listener which limits the connections accepted by the inner listener.
+///
+/// A connection takes up its room from it is accepted until it is closed. TLS listeners can be put on top of it,
+/// so that the connections are limited before the handshake.
+///
+/// # Example
+///
+/// ```no_run
+/// # use salvo_core::prelude::*;
+/// # #[tokio::main]
+/// # async fn main() {
+/// let listener = LimitListener::new(TcpListener::new("0.0.0.0:5800"))
+///     .max_connections(10_000)
+///     .max_connections_per_ip(100)
+///     .max_accept_rate(1_000);
+/// let metrics = listener.metrics();
+/// let acceptor = listener.bind().await;
+/// # }
+/// ```
+pub struct LimitListener<T> {
+    inner: T,
+    max_connections: Option<usize>,
+    max_connections_per_ip: Option<usize>,
+    max_accept_rate: Option<u32>,
+    overflow: Overflow,
+    metrics: LimitMetrics,
+}
+impl<T> LimitListener<T> {
+    /// Create a new `LimitListener` without any limit.
+    #[inline]
+    pub fn new(inner: T) -> Self {
+        LimitListener {
+            inner,
+            max_connections: None,
+            max_connections_per_ip: None,
+            max_accept_rate: None,
+            overflow: Overflow::default(),
+            metrics: LimitMetrics::default(),
+        }
+    }
+
+    /// Sets the maximum count of alive connections.
+    #[inline]
+    pub fn max_connections(mut self, max: usize) -> Self {
+        self.max_connections = Some(max);
+        self
+    }
+
+    /// Sets the maximum count of alive connections from one ip.
+    ///
+    /// The remote address is known only after a connection is accepted, so the connections over this limit are
+    /// always closed at once whatever the [`Overflow`] is.
+    #[inline]
+    pub fn max_connections_per_ip(mut self, max: usize) -> Self {
+        self.max_connections_per_ip = Some(max);
+        self
+    }
+
+    /// Sets the maximum count of connections accepted per second, bursts up to the same count are allowed.
+    #[inline]
+    pub fn max_accept_rate(mut self, per_second: u32) -> Self {
+        self.max_accept_rate = Some(per_second);
+        self
+    }
+
+    /// Sets what to do with the connections over the limit of connection count or accept rate, the default is
+    /// [`Overflow::Queue`].
+    #[inline]
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Get the [`LimitMetrics`] of this listener.
+    #[inline]
+    pub fn metrics(&self) -> LimitMetrics {
+        self.metrics.clone()
+    }
+}
+
+#[async_trait]
+impl<T> Listener for LimitListener<T>
+where
+    T: Listener + Send,
+    T::Acceptor: Send + 'static,
+{
+    type Acceptor = LimitAcceptor<T::Acceptor>;
+
+    async fn bind(self) -> Self::Acceptor {
+        self.try_bind().await.unwrap()
+    }
+
+    async fn try_bind(self) -> IoResult<Self::Acceptor> {
+        Ok(LimitAcceptor {
+            inner: self.inner.try_bind().await?,
+            semaphore: self.max_connections.map(|max| Arc::new(Semaphore::new(max))),
+            max_connections_per_ip: self.max_connections_per_ip,
+            rate_limiter: self.max_accept_rate.map(RateLimiter::new),
+            overflow: self.overflow,
+            metrics: self.metrics,
+        })
+    }
+}
+
+/// Token bucket which holds tokens of one second at most.
+struct RateLimiter {
+    rate: f64,
+    tokens: f64,
+    updated: Instant,
+}
+impl RateLimiter {
+    fn new(per_second: u32) -> Self {
+        let rate = f64::from(per_second.max(1));
+        RateLimiter {
+            rate,
+            tokens: rate,
+            updated: Instant::now(),
+        }
+    }
+
+    fn try_take(&mut self) -> bool {
+        let now = Instant::now();
+        self.tokens = (self.tokens + now.duration_since(self.updated).as_secs_f64() * self.rate).min(self.rate);
+        self.updated = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    async fn take(&mut self) {
+        while !self.try_take() {
+            tokio::time::sleep(Duration::from_secs_f64((1.0 - self.tokens) / self.rate)).await;
+        }
+    }
+}
+
+/// Acceptor of [`LimitListener`].
+pub struct LimitAcceptor<A> {
+    inner: A,
+    semaphore: Option<Arc<Semaphore>>,
+    max_connections_per_ip: Option<usize>,
+    rate_limiter: Option<RateLimiter>,
+    overflow: Overflow,
+    metrics: LimitMetrics,
+}
+impl<A> LimitAcceptor<A> {
+    fn reject<C>(&self, accepted: Accepted<C>, reason: &str) {
+        tracing::debug!(remote_addr = %accepted.remote_addr, reason, "connection rejected");
+        self.metrics.0.rejected.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[async_trait]
+impl<A> Acceptor for LimitAcceptor<A>
+where
+    A: Acceptor + Send + 'static,
+{
+    type Conn = LimitedStream<A::Conn>;
+
+    #[inline]
+    fn holdings(&self) -> &[Holding] {
+        self.inner.holdings()
+    }
+
+    async fn accept(&mut self) -> IoResult<Accepted<Self::Conn>> {
+        loop {
+            let mut permit = None;
+            if self.overflow == Overflow::Queue {
+                if let Some(semaphore) = &self.semaphore {
+                    permit = Some(
+                        semaphore
+                            .clone()
+                            .acquire_owned()
+                            .await
+                            .expect("semaphore should not be closed"),
+                    );
+                }
+                if let Some(rate_limiter) = &mut self.rate_limiter {
+                    rate_limiter.take().await;
+                }
+            }
+            let accepted = self.inner.accept().await?;
+            if self.overflow == Overflow::Close {
+                if let Some(semaphore) = &self.semaphore {
+                    match semaphore.clone().try_acquire_owned() {
+                        Ok(acquired) => permit = Some(acquired),
+                        Err(_) => {
+                            self.reject(accepted, "too many connections");
+                            continue;
+                        }
+                    }
+                }
+                if let Some(rate_limiter) = &mut self.rate_limiter {
+                    if !rate_limiter.try_take() {
+                        self.reject(accepted, "accept rate exceeded");
+                        continue;
+                    }
+                }
+            }
+
+            let ip = accepted.remote_addr.clone().into_std().map(|addr| addr.ip());
+            if let Some(ip) = ip {
+                let mut per_ip = self.metrics.0.per_ip.lock();
+                let count = per_ip.entry(ip).or_default();
+                if matches!(self.max_connections_per_ip, Some(max) if *count >= max) {
+                    drop(per_ip);
+                    self.reject(accepted, "too many connections from the ip");
+                    continue;
+                }
+                *count += 1;
+            }
+            self.metrics.0.alive.fetch_add(1, Ordering::AcqRel);
+            let guard = ConnGuard {
+                metrics: self.metrics.clone(),
+                ip,
+                _permit: permit,
+            };
+            return Ok(accepted.map_conn(|inner| LimitedStream { inner, guard }));
+        }
+    }
+}
+
+/// Releases the room of a connection when it is dropped.
+struct ConnGuard {
+    metrics: LimitMetrics,
+    ip: Option<IpAddr>,
+    _permit: Option<OwnedSemaphorePermit>,
+}
+impl Drop for ConnGuard {
+    fn drop(&mut self) {
+        if let Some(ip) = self.ip {
+            let mut per_ip = self.metrics.0.per_ip.lock();
+            if let Some(count) = per_ip.get_mut(&ip) {
+                *count -= 1;
+                if *count == 0 {
+                    per_ip.remove(&ip);
+                }
+            }
+        }
+        self.metrics.0.alive.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// A I/O stream for LimitListener.
+pub struct LimitedStream<C> {
+    inner: C,
+    guard: ConnGuard,
+}
+
+impl<C> AsyncRead for LimitedStream<C>
+where
+    C: AsyncRead + Send + Unpin + 'static,
+{
+    #[inline]
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<IoResult<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_read(cx, buf)
+    }
+}
+
+impl<C> AsyncWrite for LimitedStream<C>
+where
+    C: AsyncWrite + Send + Unpin + 'static,
+{
+    #[inline]
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<IoResult<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+#[async_trait]
+impl<C> HttpConnection for LimitedStream<C>
+where
+    C: HttpConnection + Send,
+{
+    async fn version(&mut self) -> Option<Version> {
+        self.inner.version().await
+    }
+    async fn serve(
+        self,
+        handler: HyperHandler,
+        builders: Arc<HttpBuilders>,
+        graceful_stop_token: CancellationToken,
+    ) -> IoResult<()> {
+        let LimitedStream { inner, guard } = self;
+        let result = inner.serve(handler, builders, graceful_stop_token).await;
+        drop(guard);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpStream;
+
+    use super::*;
+    use crate::conn::TcpListener;
+
+    #[tokio::test]
+    async fn test_limit_listener() {
+        let listener = LimitListener::new(TcpListener::new("127.0.0.1:0"))
+            .max_connections(2)
+            .max_connections_per_ip(1);
+        let metrics = listener.metrics();
+        let mut acceptor = listener.bind().await;
+        let addr = acceptor.holdings()[0].local_addr.clone().into_std().unwrap();
+
+        let _stream1 = TcpStream::connect(addr).await.unwrap();
+        let conn1 = acceptor.accept().await.unwrap().conn;
+        assert_eq!(metrics.alive_connections(), 1);
+        assert_eq!(metrics.ip_connections(addr.ip()), 1);
+
+        // The second connection from the same ip is closed at once.
+        let mut stream2 = TcpStream::connect(addr).await.unwrap();
+        let accept = tokio::spawn(async move {
+            let accepted = acceptor.accept().await.unwrap();
+            (acceptor, accepted.conn)
+        });
+        assert_eq!(stream2.read(&mut [0; 8]).await.unwrap(), 0);
+        assert_eq!(metrics.rejected_connections(), 1);
+
+        // The room is released after the first connection is closed.
+        drop(conn1);
+        assert_eq!(metrics.alive_connections(), 0);
+        let _stream3 = TcpStream::connect(addr).await.unwrap();
+        let (_acceptor, _conn3) = accept.await.unwrap();
+        assert_eq!(metrics.alive_connections(), 1);
+        assert_eq!(metrics.rejected_connections(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_limit_listener_queue() {
+        let listener = LimitListener::new(TcpListener::new("127.0.0.1:0")).max_connections(1);
+        let mut acceptor = listener.bind().await;
+        let addr = acceptor.holdings()[0].local_addr.clone().into_std().unwrap();
+
+        let _stream1 = TcpStream::connect(addr).await.unwrap();
+        let conn1 = acceptor.accept().await.unwrap().conn;
+        let _stream2 = TcpStream::connect(addr).await.unwrap();
+        assert!(tokio::time::timeout(Duration::from_millis(100), acceptor.accept())
+            .await
+            .is_err());
+        drop(conn1);
+        assert!(acceptor.accept().await.is_ok());
+    }
+}
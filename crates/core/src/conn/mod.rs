@@ -54,6 +54,12 @@ pub use tcp::TcpListener;
 mod joined;
 pub use joined::JoinedListener;
 
+pub mod limit;
+pub use limit::LimitListener;
+
+pub mod proxy_protocol;
+pub use proxy_protocol::ProxyProtocolListener;
+
 mod proto;
 pub use proto::HttpBuilders;
 
@@ -65,11 +71,12 @@ cfg_feature! {
 cfg_feature! {
     #![any(feature = "rustls", feature = "acme")]
     mod sealed {
-        use std::io::{Error as IoError, ErrorKind, Result as IoResult};
+        use std::io::Result as IoResult;
         use std::sync::Arc;
 
         use tokio_rustls::server::TlsStream;
         use tokio::io::{AsyncRead, AsyncWrite};
+        use tokio_util::sync::CancellationToken;
 
         use crate::async_trait;
         use crate::service::HyperHandler;
@@ -85,19 +92,21 @@ cfg_feature! {
             async fn version(&mut self) -> Option<Version> {
                 self.get_ref().1.alpn_protocol().map(version_from_alpn)
             }
-            async fn serve(self, handler: HyperHandler, builders: Arc<HttpBuilders>) -> IoResult<()> {
+            async fn serve(
+                self,
+                handler: HyperHandler,
+                builders: Arc<HttpBuilders>,
+                graceful_stop_token: CancellationToken,
+            ) -> IoResult<()> {
                 #[cfg(not(feature = "http2"))]
                 {
                     let _ = handler;
                     let _ = builders;
+                    let _ = graceful_stop_token;
                     panic!("http2 feature is required");
                 }
                 #[cfg(feature = "http2")]
-                builders
-                    .http2
-                    .serve_connection(self, handler)
-                    .await
-                    .map_err(|e| IoError::new(ErrorKind::Other, e.to_string()))
+                builders.serve_http2(self, handler, graceful_stop_token).await
             }
         }
     }
@@ -9,6 +9,7 @@ use futures_util::{Stream, StreamExt};
 use http::uri::Scheme;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio_native_tls::TlsStream;
+use tokio_util::sync::CancellationToken;
 
 use crate::async_trait;
 use crate::conn::Holding;
@@ -64,19 +65,21 @@ where
     async fn version(&mut self) -> Option<Version> {
         self.get_ref().negotiated_alpn().ok().flatten().map(version_from_alpn)
     }
-    async fn serve(self, handler: HyperHandler, builders: Arc<HttpBuilders>) -> IoResult<()> {
+    async fn serve(
+        self,
+        handler: HyperHandler,
+        builders: Arc<HttpBuilders>,
+        graceful_stop_token: CancellationToken,
+    ) -> IoResult<()> {
         #[cfg(not(feature = "http2"))]
         {
             let _ = handler;
             let _ = builders;
+            let _ = graceful_stop_token;
             panic!("http2 feature is required");
         }
         #[cfg(feature = "http2")]
-        builders
-            .http2
-            .serve_connection(self, handler)
-            .await
-            .map_err(|e| IoError::new(ErrorKind::Other, e.to_string()))
+        builders.serve_http2(self, handler, graceful_stop_token).await
     }
 }
 
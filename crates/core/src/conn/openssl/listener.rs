@@ -11,6 +11,7 @@ use openssl::ssl::{Ssl, SslAcceptor};
 use tokio::io::ErrorKind;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio_openssl::SslStream;
+use tokio_util::sync::CancellationToken;
 
 use super::OpensslConfig;
 
@@ -98,19 +99,21 @@ where
     async fn version(&mut self) -> Option<Version> {
         self.ssl().selected_alpn_protocol().map(version_from_alpn)
     }
-    async fn serve(self, handler: HyperHandler, builders: Arc<HttpBuilders>) -> IoResult<()> {
+    async fn serve(
+        self,
+        handler: HyperHandler,
+        builders: Arc<HttpBuilders>,
+        graceful_stop_token: CancellationToken,
+    ) -> IoResult<()> {
         #[cfg(not(feature = "http2"))]
         {
             let _ = handler;
             let _ = builders;
+            let _ = graceful_stop_token;
             panic!("http2 feature is required");
         }
         #[cfg(feature = "http2")]
-        builders
-            .http2
-            .serve_connection(self, handler)
-            .await
-            .map_err(|e| IoError::new(ErrorKind::Other, e.to_string()))
+        builders.serve_http2(self, handler, graceful_stop_token).await
     }
 }
 
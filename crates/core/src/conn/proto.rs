@@ -1,12 +1,21 @@
-#[cfg(feature = "http2")]
-use crate::runtimes::TokioExecutor;
+#[cfg(any(feature = "http1", feature = "http2"))]
+use std::io::{Error as IoError, ErrorKind, Result as IoResult};
+
 #[cfg(feature = "http1")]
 use hyper::server::conn::http1;
 #[cfg(feature = "http2")]
 use hyper::server::conn::http2;
+#[cfg(any(feature = "http1", feature = "http2"))]
+use tokio::io::{AsyncRead, AsyncWrite};
+#[cfg(any(feature = "http1", feature = "http2"))]
+use tokio_util::sync::CancellationToken;
 
 #[cfg(feature = "quinn")]
 use crate::conn::quinn;
+#[cfg(feature = "http2")]
+use crate::runtimes::TokioExecutor;
+#[cfg(any(feature = "http1", feature = "http2"))]
+use crate::service::HyperHandler;
 
 #[doc(hidden)]
 pub struct HttpBuilders {
@@ -17,3 +26,56 @@ pub struct HttpBuilders {
     #[cfg(feature = "quinn")]
     pub(crate) quinn: quinn::Builder,
 }
+
+impl HttpBuilders {
+    /// Serve HTTP/1 connection, the connection is closed after the in-flight request when `graceful_stop_token`
+    /// is cancelled.
+    #[cfg(feature = "http1")]
+    pub(crate) async fn serve_http1<I>(
+        &self,
+        io: I,
+        handler: HyperHandler,
+        graceful_stop_token: CancellationToken,
+    ) -> IoResult<()>
+    where
+        I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let conn = self.http1.serve_connection(io, handler).with_upgrades();
+        tokio::pin!(conn);
+        tokio::select! {
+            result = conn.as_mut() => result,
+            _ = graceful_stop_token.cancelled() => {
+                conn.as_mut().graceful_shutdown();
+                conn.await
+            }
+        }
+        .map_err(|e| IoError::new(ErrorKind::Other, e.to_string()))
+    }
+
+    /// Serve HTTP/2 connection, `GOAWAY` is sent to the peer when `graceful_stop_token` is cancelled and the
+    /// connection is closed after the in-flight streams.
+    #[cfg(all(
+        feature = "http2",
+        any(feature = "rustls", feature = "native-tls", feature = "openssl", feature = "acme")
+    ))]
+    pub(crate) async fn serve_http2<I>(
+        &self,
+        io: I,
+        handler: HyperHandler,
+        graceful_stop_token: CancellationToken,
+    ) -> IoResult<()>
+    where
+        I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let conn = self.http2.serve_connection(io, handler);
+        tokio::pin!(conn);
+        tokio::select! {
+            result = conn.as_mut() => result,
+            _ = graceful_stop_token.cancelled() => {
+                conn.as_mut().graceful_shutdown();
+                conn.await
+            }
+        }
+        .map_err(|e| IoError::new(ErrorKind::Other, e.to_string()))
+    }
+}
@@ -0,0 +1,659 @@
+//! ProxyProtocolListener and it's implements.
+use std::io::{Error as IoError, ErrorKind, Result as IoResult};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr as StdSocketAddr};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bytes::{Buf, Bytes, BytesMut};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, ReadBuf};
+use tokio::task::JoinSet;
+use tokio::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::async_trait;
+use crate::conn::{Holding, HttpBuilders};
+use crate::http::{HttpConnection, Version};
+use crate::service::HyperHandler;
+
+use super::{Accepted, Acceptor, Listener};
+
+#[cfg(any(feature = "rustls", feature = "native-tls", feature = "openssl"))]
+use crate::conn::IntoConfigStream;
+
+#[cfg(feature = "rustls")]
+use crate::conn::rustls::{RustlsConfig, RustlsListener};
+
+#[cfg(feature = "native-tls")]
+use crate::conn::native_tls::{NativeTlsConfig, NativeTlsListener};
+
+#[cfg(feature = "openssl")]
+use crate::conn::openssl::{OpensslConfig, OpensslListener};
+
+#[cfg(feature = "acme")]
+use crate::conn::acme::AcmeListener;
+
+const V1_PREFIX: &[u8] = b"PROXY ";
+/// The longest v1 header, including the trailing CRLF.
+const V1_MAX_LEN: usize = 107;
+const V2_SIGNATURE: &[u8] = b"\r\n\r\n\0\r\nQUIT\n";
+const V2_FIXED_LEN: usize = 16;
+
+#[derive(Debug, Eq, PartialEq)]
+enum Parsed {
+    /// More bytes are required.
+    Incomplete,
+    /// The stream does not start with a PROXY protocol header.
+    Missing,
+    /// A header of `len` bytes is found, `source` is `None` for `LOCAL` and `UNKNOWN` headers and unsupported
+    /// address families.
+    Header { len: usize, source: Option<StdSocketAddr> },
+}
+
+fn invalid_header(reason: &str) -> IoError {
+    IoError::new(
+        ErrorKind::InvalidData,
+        format!("invalid proxy protocol header: {reason}"),
+    )
+}
+
+fn parse(buf: &[u8]) -> IoResult<Parsed> {
+    if buf.is_empty() {
+        return Ok(Parsed::Incomplete);
+    }
+    let len = buf.len().min(V2_SIGNATURE.len());
+    if buf[..len] == V2_SIGNATURE[..len] {
+        return parse_v2(buf);
+    }
+    let len = buf.len().min(V1_PREFIX.len());
+    if buf[..len] == V1_PREFIX[..len] {
+        return parse_v1(buf);
+    }
+    Ok(Parsed::Missing)
+}
+
+fn parse_v1(buf: &[u8]) -> IoResult<Parsed> {
+    let end = match buf.windows(2).take(V1_MAX_LEN - 1).position(|w| w == b"\r\n") {
+        Some(end) => end,
+        None if buf.len() >= V1_MAX_LEN => return Err(invalid_header("v1 header is too long")),
+        None => return Ok(Parsed::Incomplete),
+    };
+    let line = std::str::from_utf8(&buf[..end]).map_err(|_| invalid_header("v1 header is not utf-8"))?;
+    let parts = line.split(' ').collect::<Vec<_>>();
+    let source = match parts.get(1).copied() {
+        Some("UNKNOWN") => None,
+        Some(proto @ ("TCP4" | "TCP6")) => {
+            if parts.len() != 6 {
+                return Err(invalid_header("v1 header has wrong count of fields"));
+            }
+            let ip = parts[2]
+                .parse::<IpAddr>()
+                .map_err(|_| invalid_header("v1 header has invalid source address"))?;
+            let port = parts[4]
+                .parse::<u16>()
+                .map_err(|_| invalid_header("v1 header has invalid source port"))?;
+            if ip.is_ipv4() != (proto == "TCP4") {
+                return Err(invalid_header("v1 header has mismatched address family"));
+            }
+            Some(StdSocketAddr::new(ip, port))
+        }
+        _ => return Err(invalid_header("v1 header has unknown protocol")),
+    };
+    Ok(Parsed::Header { len: end + 2, source })
+}
+
+fn parse_v2(buf: &[u8]) -> IoResult<Parsed> {
+    if buf.len() < V2_FIXED_LEN {
+        return Ok(Parsed::Incomplete);
+    }
+    if buf[12] >> 4 != 2 {
+        return Err(invalid_header("v2 header has unsupported version"));
+    }
+    let len = V2_FIXED_LEN + u16::from_be_bytes([buf[14], buf[15]]) as usize;
+    if buf.len() < len {
+        return Ok(Parsed::Incomplete);
+    }
+    let addrs = &buf[V2_FIXED_LEN..len];
+    let source = match buf[12] & 0x0F {
+        // LOCAL command, the connection is made by the proxy itself.
+        0x0 => None,
+        0x1 => match buf[13] >> 4 {
+            0x1 if addrs.len() >= 12 => {
+                let ip = Ipv4Addr::new(addrs[0], addrs[1], addrs[2], addrs[3]);
+                let port = u16::from_be_bytes([addrs[8], addrs[9]]);
+                Some(StdSocketAddr::new(ip.into(), port))
+            }
+            0x2 if addrs.len() >= 36 => {
+                let mut octets = [0; 16];
+                octets.copy_from_slice(&addrs[..16]);
+                let port = u16::from_be_bytes([addrs[32], addrs[33]]);
+                Some(StdSocketAddr::new(Ipv6Addr::from(octets).into(), port))
+            }
+            0x1 | 0x2 => return Err(invalid_header("v2 header has truncated addresses")),
+            // UNSPEC and UNIX families are kept as the socket address.
+            _ => None,
+        },
+        _ => return Err(invalid_header("v2 header has unsupported command")),
+    };
+    Ok(Parsed::Header { len, source })
+}
+
+/// Read the PROXY protocol header, returns the parsed result and the bytes read after the header.
+async fn read_header<C>(conn: &mut C) -> IoResult<(Parsed, Bytes)>
+where
+    C: AsyncRead + Unpin,
+{
+    let mut buf = BytesMut::with_capacity(V1_MAX_LEN);
+    loop {
+        match parse(&buf)? {
+            Parsed::Incomplete => {
+                if conn.read_buf(&mut buf).await? == 0 {
+                    return Err(IoError::new(
+                        ErrorKind::UnexpectedEof,
+                        "connection closed before proxy protocol header",
+                    ));
+                }
+            }
+            parsed => {
+                if let Parsed::Header { len, .. } = parsed {
+                    buf.advance(len);
+                }
+                return Ok((parsed, buf.freeze()));
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct TrustedNetwork {
+    ip: IpAddr,
+    prefix_len: u8,
+}
+impl TrustedNetwork {
+    fn contains(&self, ip: IpAddr) -> bool {
+        fn prefix_eq(a: u128, b: u128, bits: u32, prefix_len: u8) -> bool {
+            let prefix_len = u32::from(prefix_len).min(bits);
+            prefix_len == 0 || (a >> (bits - prefix_len)) == (b >> (bits - prefix_len))
+        }
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+            ip => ip,
+        };
+        match (self.ip, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                prefix_eq(u32::from(net).into(), u32::from(ip).into(), 32, self.prefix_len)
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => prefix_eq(net.into(), ip.into(), 128, self.prefix_len),
+            _ => false,
+        }
+    }
+}
+
+/// Listener which reads [PROXY protocol](https://www.haproxy.org/download/2.8/doc/proxy-protocol.txt) v1 and
+/// v2 headers, the source address in the header is used as the remote address of the connection.
+///
+/// It should wrap a plain listener like [`TcpListener`](crate::conn::TcpListener), TLS listeners can be put on top
+/// of it, so the header is read before the handshake.
+///
+/// Connections without the header are served with the socket address unless it is in strict mode.
+///
+/// **Warning**: Only the headers sent from the sources set by [`trust`](ProxyProtocolListener::trust) or
+/// [`trust_network`](ProxyProtocolListener::trust_network) are read, no header is read until one is set. Any client
+/// which can reach a trusted source directly is able to spoof its address, so only trust your proxies.
+///
+/// Headers are read in background tasks, so a client which is slow to send the header does not hold up other
+/// connections.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # use salvo_core::prelude::*;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let acceptor = ProxyProtocolListener::new(TcpListener::new("0.0.0.0:5800"))
+///     .strict(true)
+///     .trust_network(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0)), 8)
+///     .bind()
+///     .await;
+/// # }
+/// ```
+pub struct ProxyProtocolListener<T> {
+    inner: T,
+    strict: bool,
+    trusted: Vec<TrustedNetwork>,
+    read_timeout: Duration,
+}
+impl<T> ProxyProtocolListener<T> {
+    /// Create a new `ProxyProtocolListener`.
+    #[inline]
+    pub fn new(inner: T) -> Self {
+        ProxyProtocolListener {
+            inner,
+            strict: false,
+            trusted: vec![],
+            read_timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// Sets whether connections without the header, or from untrusted sources, are closed.
+    #[inline]
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Trust the headers sent from the ip.
+    #[inline]
+    pub fn trust(self, ip: IpAddr) -> Self {
+        let prefix_len = if ip.is_ipv4() { 32 } else { 128 };
+        self.trust_network(ip, prefix_len)
+    }
+
+    /// Trust the headers sent from the network, e.g. `10.0.0.0/8` is `trust_network(ip, 8)`.
+    ///
+    /// **Warning**: All the hosts in the network are able to spoof the remote address, a prefix length of `0`
+    /// trusts every source, do not use it unless the listener is only reachable by your proxies.
+    #[inline]
+    pub fn trust_network(mut self, ip: IpAddr, prefix_len: u8) -> Self {
+        self.trusted.push(TrustedNetwork { ip, prefix_len });
+        self
+    }
+
+    /// Sets the timeout of reading the header, the default is 5 seconds.
+    #[inline]
+    pub fn read_timeout(mut self, read_timeout: Duration) -> Self {
+        self.read_timeout = read_timeout;
+        self
+    }
+
+    cfg_feature! {
+        #![feature = "rustls"]
+
+        /// Creates a new `RustlsListener` from current `ProxyProtocolListener`.
+        #[inline]
+        pub fn rustls<C>(self, config_stream: C) -> RustlsListener<C, Self>
+        where
+            C: IntoConfigStream<RustlsConfig> + Send + 'static,
+            T: Listener + Send,
+            T::Acceptor: Send + 'static,
+        {
+            RustlsListener::new(config_stream, self)
+        }
+    }
+
+    cfg_feature! {
+        #![feature = "native-tls"]
+
+        /// Creates a new `NativeTlsListener` from current `ProxyProtocolListener`.
+        #[inline]
+        pub fn native_tls<C>(self, config_stream: C) -> NativeTlsListener<C, Self>
+        where
+            C: IntoConfigStream<NativeTlsConfig> + Send + 'static,
+            T: Listener + Send,
+            T::Acceptor: Send + 'static,
+        {
+            NativeTlsListener::new(config_stream, self)
+        }
+    }
+
+    cfg_feature! {
+        #![feature = "openssl"]
+
+        /// Creates a new `OpensslListener` from current `ProxyProtocolListener`.
+        #[inline]
+        pub fn openssl<C>(self, config_stream: C) -> OpensslListener<C, Self>
+        where
+            C: IntoConfigStream<OpensslConfig> + Send + 'static,
+            T: Listener + Send,
+            T::Acceptor: Send + 'static,
+        {
+            OpensslListener::new(config_stream, self)
+        }
+    }
+
+    cfg_feature! {
+        #![feature = "acme"]
+
+        /// Creates a new `AcmeListener` from current `ProxyProtocolListener`.
+        #[inline]
+        pub fn acme(self) -> AcmeListener<Self> {
+            AcmeListener::new(self)
+        }
+    }
+}
+
+#[async_trait]
+impl<T> Listener for ProxyProtocolListener<T>
+where
+    T: Listener + Send,
+    T::Acceptor: Send + 'static,
+{
+    type Acceptor = ProxyProtocolAcceptor<T::Acceptor>;
+
+    async fn bind(self) -> Self::Acceptor {
+        self.try_bind().await.unwrap()
+    }
+
+    async fn try_bind(self) -> IoResult<Self::Acceptor> {
+        if self.trusted.is_empty() {
+            tracing::warn!("no trusted source is set for proxy protocol listener, headers will not be read");
+        }
+        Ok(ProxyProtocolAcceptor {
+            inner: self.inner.try_bind().await?,
+            strict: self.strict,
+            trusted: self.trusted,
+            read_timeout: self.read_timeout,
+            pending: JoinSet::new(),
+        })
+    }
+}
+
+/// Acceptor of [`ProxyProtocolListener`].
+pub struct ProxyProtocolAcceptor<A: Acceptor> {
+    inner: A,
+    strict: bool,
+    trusted: Vec<TrustedNetwork>,
+    read_timeout: Duration,
+    /// Connections whose header is being read.
+    pending: JoinSet<Option<Accepted<ProxyProtocolStream<A::Conn>>>>,
+}
+impl<A: Acceptor> ProxyProtocolAcceptor<A> {
+    fn is_trusted(&self, remote_addr: &super::SocketAddr) -> bool {
+        match remote_addr.clone().into_std() {
+            Some(addr) => self.trusted.iter().any(|network| network.contains(addr.ip())),
+            None => false,
+        }
+    }
+}
+
+#[async_trait]
+impl<A> Acceptor for ProxyProtocolAcceptor<A>
+where
+    A: Acceptor + Send + 'static,
+{
+    type Conn = ProxyProtocolStream<A::Conn>;
+
+    #[inline]
+    fn holdings(&self) -> &[Holding] {
+        self.inner.holdings()
+    }
+
+    async fn accept(&mut self) -> IoResult<Accepted<Self::Conn>> {
+        loop {
+            tokio::select! {
+                accepted = self.inner.accept() => {
+                    let accepted = accepted?;
+                    if !self.is_trusted(&accepted.remote_addr) {
+                        if self.strict {
+                            tracing::debug!(remote_addr = %accepted.remote_addr, "connection from untrusted source rejected");
+                            continue;
+                        }
+                        return Ok(accepted.map_conn(|inner| ProxyProtocolStream::new(inner, Bytes::new())));
+                    }
+                    self.pending.spawn(accept_header(accepted, self.strict, self.read_timeout));
+                }
+                Some(result) = self.pending.join_next(), if !self.pending.is_empty() => {
+                    if let Ok(Some(accepted)) = result {
+                        return Ok(accepted);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Read the header of an accepted connection, returns `None` if the connection should be closed.
+async fn accept_header<C>(
+    mut accepted: Accepted<C>,
+    strict: bool,
+    read_timeout: Duration,
+) -> Option<Accepted<ProxyProtocolStream<C>>>
+where
+    C: HttpConnection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+{
+    let (parsed, rest) = match tokio::time::timeout(read_timeout, read_header(&mut accepted.conn)).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(e)) => {
+            tracing::debug!(remote_addr = %accepted.remote_addr, error = ?e, "read proxy protocol header failed");
+            return None;
+        }
+        Err(_) => {
+            tracing::debug!(remote_addr = %accepted.remote_addr, "read proxy protocol header timeout");
+            return None;
+        }
+    };
+    match parsed {
+        Parsed::Header {
+            source: Some(source), ..
+        } => {
+            accepted.remote_addr = source.into();
+        }
+        Parsed::Missing if strict => {
+            tracing::debug!(remote_addr = %accepted.remote_addr, "connection without proxy protocol header rejected");
+            return None;
+        }
+        _ => {}
+    }
+    Some(accepted.map_conn(|inner| ProxyProtocolStream::new(inner, rest)))
+}
+
+/// A I/O stream for ProxyProtocolListener, the bytes read after the header are returned first.
+pub struct ProxyProtocolStream<C> {
+    inner: C,
+    rest: Bytes,
+}
+impl<C> ProxyProtocolStream<C> {
+    fn new(inner: C, rest: Bytes) -> Self {
+        ProxyProtocolStream { inner, rest }
+    }
+}
+
+impl<C> AsyncRead for ProxyProtocolStream<C>
+where
+    C: AsyncRead + Send + Unpin + 'static,
+{
+    #[inline]
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<IoResult<()>> {
+        let this = self.get_mut();
+        if this.rest.is_empty() {
+            Pin::new(&mut this.inner).poll_read(cx, buf)
+        } else {
+            let len = this.rest.len().min(buf.remaining());
+            buf.put_slice(&this.rest.split_to(len));
+            Poll::Ready(Ok(()))
+        }
+    }
+}
+
+impl<C> AsyncWrite for ProxyProtocolStream<C>
+where
+    C: AsyncWrite + Send + Unpin + 'static,
+{
+    #[inline]
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<IoResult<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+#[async_trait]
+impl<C> HttpConnection for ProxyProtocolStream<C>
+where
+    C: HttpConnection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
+{
+    async fn version(&mut self) -> Option<Version> {
+        self.inner.version().await
+    }
+    async fn serve(
+        self,
+        handler: HyperHandler,
+        builders: Arc<HttpBuilders>,
+        graceful_stop_token: CancellationToken,
+    ) -> IoResult<()> {
+        if self.rest.is_empty() {
+            return self.inner.serve(handler, builders, graceful_stop_token).await;
+        }
+        // The inner stream can not take the bytes read after the header back, so it is served as plain HTTP/1.
+        #[cfg(not(feature = "http1"))]
+        {
+            let _ = handler;
+            let _ = builders;
+            let _ = graceful_stop_token;
+            panic!("http1 feature is required");
+        }
+        #[cfg(feature = "http1")]
+        builders.serve_http1(self, handler, graceful_stop_token).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpStream;
+
+    use super::*;
+    use crate::conn::TcpListener;
+
+    #[test]
+    fn test_parse_v1() {
+        assert_eq!(parse(b"PROX").unwrap(), Parsed::Incomplete);
+        assert_eq!(parse(b"PROXY TCP4 1.2.3.4 5.6.7.8 11").unwrap(), Parsed::Incomplete);
+        assert_eq!(
+            parse(b"PROXY TCP4 1.2.3.4 5.6.7.8 1111 80\r\nGET").unwrap(),
+            Parsed::Header {
+                len: 36,
+                source: Some("1.2.3.4:1111".parse().unwrap())
+            }
+        );
+        assert_eq!(
+            parse(b"PROXY TCP6 ::1 ::2 1111 80\r\n").unwrap(),
+            Parsed::Header {
+                len: 28,
+                source: Some("[::1]:1111".parse().unwrap())
+            }
+        );
+        assert_eq!(
+            parse(b"PROXY UNKNOWN\r\n").unwrap(),
+            Parsed::Header { len: 15, source: None }
+        );
+        assert_eq!(parse(b"POST / HTTP/1.1\r\n").unwrap(), Parsed::Missing);
+        assert!(parse(b"PROXY TCP4 ::1 ::2 1111 80\r\n").is_err());
+        assert!(parse(b"PROXY UDP4 1.2.3.4 5.6.7.8 1111 80\r\n").is_err());
+        assert!(parse(&[b"PROXY ".as_slice(), &[b'a'; 120]].concat()).is_err());
+    }
+
+    #[test]
+    fn test_parse_v2() {
+        let mut header = V2_SIGNATURE.to_vec();
+        header.extend_from_slice(&[0x21, 0x11, 0, 12, 1, 2, 3, 4, 5, 6, 7, 8, 0x04, 0x57, 0, 80]);
+        assert_eq!(parse(&header[..20]).unwrap(), Parsed::Incomplete);
+        assert_eq!(
+            parse(&header).unwrap(),
+            Parsed::Header {
+                len: 28,
+                source: Some("1.2.3.4:1111".parse().unwrap())
+            }
+        );
+
+        let mut local = V2_SIGNATURE.to_vec();
+        local.extend_from_slice(&[0x20, 0x00, 0, 0]);
+        assert_eq!(parse(&local).unwrap(), Parsed::Header { len: 16, source: None });
+
+        let mut invalid = V2_SIGNATURE.to_vec();
+        invalid.extend_from_slice(&[0x11, 0x11, 0, 0]);
+        assert!(parse(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_trusted_network() {
+        let network = TrustedNetwork {
+            ip: "10.0.0.0".parse().unwrap(),
+            prefix_len: 8,
+        };
+        assert!(network.contains("10.1.2.3".parse().unwrap()));
+        assert!(network.contains("::ffff:10.1.2.3".parse().unwrap()));
+        assert!(!network.contains("11.1.2.3".parse().unwrap()));
+        assert!(!network.contains("::1".parse().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_proxy_protocol_listener() {
+        let mut acceptor = ProxyProtocolListener::new(TcpListener::new("127.0.0.1:0"))
+            .strict(true)
+            .trust(IpAddr::V4(Ipv4Addr::LOCALHOST))
+            .bind()
+            .await;
+        let addr = acceptor.holdings()[0].local_addr.clone().into_std().unwrap();
+
+        // An idle connection does not block the others.
+        let _idle = TcpStream::connect(addr).await.unwrap();
+        let mut stream1 = TcpStream::connect(addr).await.unwrap();
+        stream1.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+        let mut stream2 = TcpStream::connect(addr).await.unwrap();
+        stream2
+            .write_all(b"PROXY TCP4 1.2.3.4 5.6.7.8 1111 80\r\nhello")
+            .await
+            .unwrap();
+
+        // The first connection is rejected because the header is missing.
+        let Accepted {
+            mut conn, remote_addr, ..
+        } = tokio::time::timeout(Duration::from_secs(1), acceptor.accept())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(remote_addr.into_std().unwrap(), "1.2.3.4:1111".parse().unwrap());
+        let mut buf = [0; 5];
+        conn.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"hello");
+        assert_eq!(stream1.read(&mut [0; 8]).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_proxy_protocol_listener_without_trusted() {
+        let mut acceptor = ProxyProtocolListener::new(TcpListener::new("127.0.0.1:0")).bind().await;
+        let addr = acceptor.holdings()[0].local_addr.clone().into_std().unwrap();
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"PROXY TCP4 1.2.3.4 5.6.7.8 1111 80\r\nhello")
+            .await
+            .unwrap();
+        let Accepted {
+            mut conn, remote_addr, ..
+        } = tokio::time::timeout(Duration::from_secs(1), acceptor.accept())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(remote_addr.into_std().unwrap(), stream.local_addr().unwrap());
+        let mut buf = [0; 6];
+        conn.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"PROXY ");
+
+        let mut acceptor = ProxyProtocolListener::new(TcpListener::new("127.0.0.1:0"))
+            .strict(true)
+            .bind()
+            .await;
+        let addr = acceptor.holdings()[0].local_addr.clone().into_std().unwrap();
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"PROXY TCP4 1.2.3.4 5.6.7.8 1111 80\r\nhello")
+            .await
+            .unwrap();
+        assert!(tokio::time::timeout(Duration::from_millis(200), acceptor.accept())
+            .await
+            .is_err());
+        assert!(matches!(stream.read(&mut [0; 8]).await, Ok(0) | Err(_)));
+    }
+}
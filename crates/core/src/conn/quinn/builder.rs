@@ -3,26 +3,44 @@ use std::pin::Pin;
 use std::io::Result as IoResult;
 
 use futures_util::future::poll_fn;
-use futures_util::Stream;
 use h3::error::ErrorLevel;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 
-use crate::http::body::{H3ReqBody, ReqBody};
+use crate::http::body::{Body, H3ReqBody, ReqBody};
 
 /// Builder is used to serve HTTP3 connection.
 pub struct Builder;
 impl Builder {
     /// Serve HTTP3 connection.
+    ///
+    /// When `graceful_stop_token` is cancelled, `GOAWAY` is sent to the peer and this function returns after the
+    /// in-flight requests are done.
     pub async fn serve_connection(
         &self,
         mut conn: crate::conn::quinn::H3Connection,
         hyper_handler: crate::service::HyperHandler,
+        graceful_stop_token: CancellationToken,
     ) -> IoResult<()> {
+        // Every request task holds a sender, so `recv` returns `None` once all of them are finished.
+        let (alive_tx, mut alive_rx) = mpsc::channel::<()>(1);
         loop {
-            match conn.accept().await {
+            let accepted = tokio::select! {
+                accepted = conn.accept() => accepted,
+                _ = graceful_stop_token.cancelled() => {
+                    if let Err(e) = conn.shutdown(0).await {
+                        tracing::debug!(error = ?e, "send goaway failed");
+                    }
+                    break;
+                }
+            };
+            match accepted {
                 Ok(Some((request, stream))) => {
                     tracing::debug!("new request: {:#?}", request);
                     let mut hyper_handler = hyper_handler.clone();
+                    let alive_tx = alive_tx.clone();
                     tokio::spawn(async move {
+                        let _alive_tx = alive_tx;
                         let (parts, _body) = request.into_parts();
                         let (mut tx, rx) = stream.split();
                         let request = hyper::Request::from_parts(parts, ReqBody::from(H3ReqBody::new(rx)));
@@ -46,13 +64,22 @@ impl Builder {
                         }
 
                         let mut body = Pin::new(&mut body);
-                        while let Some(result) = poll_fn(|cx| body.as_mut().poll_next(cx)).await {
+                        while let Some(result) = poll_fn(|cx| body.as_mut().poll_frame(cx)).await {
                             match result {
-                                Ok(bytes) => {
-                                    if let Err(e) = tx.send_data(bytes).await {
-                                        tracing::error!(error = ?e, "unable to send data to connection peer");
+                                Ok(frame) => match frame.into_data() {
+                                    Ok(bytes) => {
+                                        if let Err(e) = tx.send_data(bytes).await {
+                                            tracing::error!(error = ?e, "unable to send data to connection peer");
+                                        }
                                     }
-                                }
+                                    Err(frame) => {
+                                        if let Ok(trailers) = frame.into_trailers() {
+                                            if let Err(e) = tx.send_trailers(trailers).await {
+                                                tracing::error!(error = ?e, "unable to send trailers to connection peer");
+                                            }
+                                        }
+                                    }
+                                },
                                 Err(e) => {
                                     tracing::error!(error = ?e, "unable to poll data from connection");
                                 }
@@ -75,6 +102,8 @@ impl Builder {
                 }
             }
         }
+        drop(alive_tx);
+        alive_rx.recv().await;
         Ok(())
     }
 }
@@ -12,6 +12,7 @@ use h3_quinn::quinn::Endpoint;
 pub use h3_quinn::quinn::ServerConfig;
 use http::uri::Scheme;
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio_util::sync::CancellationToken;
 
 use crate::async_trait;
 use crate::conn::rustls::RustlsConfig;
@@ -111,8 +112,16 @@ impl HttpConnection for H3Connection {
     async fn version(&mut self) -> Option<Version> {
         Some(Version::HTTP_3)
     }
-    async fn serve(self, handler: HyperHandler, builders: Arc<HttpBuilders>) -> IoResult<()> {
-        builders.quinn.serve_connection(self, handler).await
+    async fn serve(
+        self,
+        handler: HyperHandler,
+        builders: Arc<HttpBuilders>,
+        graceful_stop_token: CancellationToken,
+    ) -> IoResult<()> {
+        builders
+            .quinn
+            .serve_connection(self, handler, graceful_stop_token)
+            .await
     }
 }
 
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use std::vec;
 
 use tokio::net::{TcpListener as TokioTcpListener, TcpStream, ToSocketAddrs};
+use tokio_util::sync::CancellationToken;
 
 use crate::async_trait;
 use crate::conn::Holding;
@@ -131,20 +132,21 @@ impl HttpConnection for TcpStream {
     async fn version(&mut self) -> Option<Version> {
         Some(Version::HTTP_11)
     }
-    async fn serve(self, handler: HyperHandler, builders: Arc<HttpBuilders>) -> IoResult<()> {
+    async fn serve(
+        self,
+        handler: HyperHandler,
+        builders: Arc<HttpBuilders>,
+        graceful_stop_token: CancellationToken,
+    ) -> IoResult<()> {
         #[cfg(not(feature = "http1"))]
         {
             let _ = handler;
             let _ = builders;
+            let _ = graceful_stop_token;
             panic!("http1 feature is required");
         }
         #[cfg(feature = "http1")]
-        builders
-            .http1
-            .serve_connection(self, handler)
-            .with_upgrades()
-            .await
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
+        builders.serve_http1(self, handler, graceful_stop_token).await
     }
 }
 
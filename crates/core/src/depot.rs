@@ -1,6 +1,10 @@
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::fmt::{self, Formatter};
+use std::sync::Arc;
+
+use crate::routing::mount::MountPrefix;
+use crate::routing::{CompiledRouter, UrlForError};
 
 /// Depot is for store temp data of current request. Each handler can read or write data to it.
 ///
@@ -73,6 +77,12 @@ impl Depot {
         self.get(&format!("{:?}", TypeId::of::<T>()))
     }
 
+    /// Take a value previous inject to the depot.
+    #[inline]
+    pub fn scrape<T: Any + Send + Sync>(&mut self) -> Option<T> {
+        self.remove(&format!("{:?}", TypeId::of::<T>()))
+    }
+
     /// Inserts a key-value pair into the depot.
     #[inline]
     pub fn insert<K, V>(&mut self, key: K, value: V) -> &mut Self
@@ -108,6 +118,28 @@ impl Depot {
         self.map.remove(key).and_then(|b| b.downcast::<V>().ok()).map(|b| *b)
     }
 
+    /// Generate url for the router with the given name, see [`CompiledRouter::url_for`].
+    ///
+    /// The router is injected into depot by [`Service`](crate::Service) when it contains any named router,
+    /// [`UrlForError::UnknownRoute`] is returned if there is no router in depot.
+    /// The url is prefixed with [`Request::mount_prefix`](crate::Request::mount_prefix) in a mounted service.
+    #[inline]
+    pub fn url_for(
+        &self,
+        name: &str,
+        params: &[(&str, &str)],
+        queries: &[(&str, &str)],
+    ) -> Result<String, UrlForError> {
+        let url = self
+            .obtain::<Arc<CompiledRouter>>()
+            .ok_or_else(|| UrlForError::UnknownRoute(name.to_owned()))?
+            .url_for(name, params, queries)?;
+        match self.obtain::<MountPrefix>() {
+            Some(prefix) => Ok(format!("{}{url}", prefix.0)),
+            None => Ok(url),
+        }
+    }
+
     /// Transfer all data to a new instance.
     #[inline]
     pub fn transfer(&mut self) -> Self {
@@ -176,4 +208,24 @@ mod test {
             .unwrap();
         assert_eq!(content, "Hello client");
     }
+
+    #[tokio::test]
+    async fn test_depot_url_for() {
+        #[handler]
+        async fn show_user(depot: &mut Depot) -> String {
+            depot
+                .url_for("user.show", &[("id", "12")], &[("tab", "emails")])
+                .unwrap()
+        }
+        let router = Router::with_path("users/<id>").name("user.show").get(show_user);
+        let service = Service::new(router);
+
+        let content = TestClient::get("http://127.0.0.1:5800/users/1")
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, "/users/12?tab=emails");
+    }
 }
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::str::FromStr;
 use std::vec;
 
@@ -24,6 +25,21 @@ pub enum SourceFrom {
     Request,
 }
 
+impl SourceFrom {
+    /// Get the name of this source, it is the same as the name used in `#[extract(source(from = ...))]`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Param => "param",
+            Self::Query => "query",
+            Self::Header => "header",
+            #[cfg(feature = "cookie")]
+            Self::Cookie => "cookie",
+            Self::Body => "body",
+            Self::Request => "request",
+        }
+    }
+}
+
 impl FromStr for SourceFrom {
     type Err = crate::Error;
 
@@ -117,6 +133,21 @@ pub enum SourceFormat {
     Json,
     /// Request format means this field will extract from the request.
     Request,
+    /// Signed cookie format, the cookie value is verified with the request's cookie key.
+    #[cfg(feature = "cookie")]
+    Signed,
+    /// Private cookie format, the cookie value is decrypted with the request's cookie key.
+    #[cfg(feature = "cookie")]
+    Private,
+    /// MessagePack format, the field is extracted from MessagePack body.
+    #[cfg(feature = "msgpack")]
+    MsgPack,
+    /// CBOR format, the field is extracted from CBOR body.
+    #[cfg(feature = "cbor")]
+    Cbor,
+    /// Xml format, the field is extracted from the child element of xml body's root element.
+    #[cfg(feature = "xml")]
+    Xml,
 }
 
 impl FromStr for SourceFormat {
@@ -127,6 +158,16 @@ impl FromStr for SourceFormat {
             "multimap" => Ok(Self::MultiMap),
             "json" => Ok(Self::Json),
             "request" => Ok(Self::Request),
+            #[cfg(feature = "cookie")]
+            "signed" => Ok(Self::Signed),
+            #[cfg(feature = "cookie")]
+            "private" => Ok(Self::Private),
+            #[cfg(feature = "msgpack")]
+            "msgpack" => Ok(Self::MsgPack),
+            #[cfg(feature = "cbor")]
+            "cbor" => Ok(Self::Cbor),
+            #[cfg(feature = "xml")]
+            "xml" => Ok(Self::Xml),
             _ => Err(crate::Error::Other("invalid source format".into())),
         }
     }
@@ -185,6 +226,27 @@ impl Metadata {
         self.rename_all = Some(rename_all);
         self
     }
+
+    /// Get the field by it's name.
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Get the name of field in request, field's rename is used first, then the rename rule of this type.
+    pub fn request_name(&self, field: &Field) -> Cow<'static, str> {
+        if let Some(rename) = field.rename {
+            Cow::from(rename)
+        } else if let Some(rename_all) = self.rename_all {
+            rename_all.rename(field.name).into()
+        } else {
+            Cow::from(field.name)
+        }
+    }
+
+    /// Get the first source of field, the default sources are used if field has no sources.
+    pub fn field_source<'a>(&'a self, field: &'a Field) -> Option<&'a Source> {
+        field.sources.first().or_else(|| self.default_sources.first())
+    }
 }
 
 /// Information about struct field.
@@ -290,6 +352,16 @@ mod tests {
             ("multimap", SourceFormat::MultiMap),
             ("json", SourceFormat::Json),
             ("request", SourceFormat::Request),
+            #[cfg(feature = "cookie")]
+            ("signed", SourceFormat::Signed),
+            #[cfg(feature = "cookie")]
+            ("private", SourceFormat::Private),
+            #[cfg(feature = "msgpack")]
+            ("msgpack", SourceFormat::MsgPack),
+            #[cfg(feature = "cbor")]
+            ("cbor", SourceFormat::Cbor),
+            #[cfg(feature = "xml")]
+            ("xml", SourceFormat::Xml),
         ] {
             assert_eq!(key.parse::<SourceFormat>().unwrap(), value);
         }
@@ -64,11 +64,13 @@
 
 /// Metadata types.
 pub mod metadata;
+pub mod validate;
 pub use metadata::Metadata;
 
 use async_trait::async_trait;
 use serde::Deserialize;
 
+use crate::http::errors::InvalidParam;
 use crate::http::{ParseError, Request};
 use crate::serde::from_request;
 
@@ -78,9 +80,16 @@ pub trait Extractible<'de>: Deserialize<'de> {
     /// Metadata for Extractible type.
     fn metadata() -> &'de Metadata;
 
-    /// Extract data from request.
+    /// Extract data from request, the extracted data is validated before returned.
     async fn extract(req: &'de mut Request) -> Result<Self, ParseError> {
-        from_request(req, Self::metadata()).await
+        let data: Self = from_request(req, Self::metadata()).await?;
+        data.validate().map_err(ParseError::Validation)?;
+        Ok(data)
+    }
+    /// Validate the extracted data, returns all the invalid fields. It is generated by `#[extract(validate(...))]`
+    /// attributes when derive `Extractible`.
+    fn validate(&self) -> Result<(), Vec<InvalidParam>> {
+        Ok(())
     }
     /// Extract data from request with a argument. This function used in macros internal.
     async fn extract_with_arg(req: &'de mut Request, _arg: &str) -> Result<Self, ParseError> {
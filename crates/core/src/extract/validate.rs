@@ -0,0 +1,448 @@
+//! Validators used by `#[extract(validate(...))]` attributes of [`Extractible`](super::Extractible) types.
+//!
+//! Each validator returns the reason if the value is invalid, the reasons are collected into
+//! [`ParseError::Validation`](crate::http::ParseError::Validation) with field's name and source.
+//!
+//! ```
+//! # use salvo_core::prelude::*;
+//! # use salvo_core::http::form::FilePart;
+//! # use serde::Deserialize;
+//! #[derive(Deserialize, Extractible, Debug)]
+//! #[extract(default_source(from = "query"))]
+//! struct Register {
+//!     #[extract(validate(length(min = 3, max = 20), regex = "^[a-z0-9_]+$"))]
+//!     username: String,
+//!     #[extract(validate(email))]
+//!     email: String,
+//!     #[extract(validate(range(min = 18, max = 150)))]
+//!     age: u8,
+//!     kind: String,
+//!     #[extract(validate(required_if(field = "kind", value = "company")))]
+//!     company: Option<String>,
+//! }
+//!
+//! #[derive(Deserialize, Extractible, Debug)]
+//! #[extract(default_source(from = "body"))]
+//! struct Upload {
+//!     title: String,
+//!     #[extract(validate(file(max_size = 1048576, content_types = ["image/png", "image/jpeg"])))]
+//!     cover: FilePart,
+//!     #[extract(validate(file(max_size = 10485760, max_count = 5)))]
+//!     attachments: Vec<FilePart>,
+//! }
+//! ```
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+
+use regex::Regex;
+
+use super::Metadata;
+use crate::http::errors::InvalidParam;
+use crate::http::form::FilePart;
+
+/// Types which have length can be validated by `length` validator.
+pub trait HasLength {
+    /// Get the length, it is the count of chars for strings.
+    fn length(&self) -> usize;
+}
+impl HasLength for str {
+    #[inline]
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+impl HasLength for String {
+    #[inline]
+    fn length(&self) -> usize {
+        self.as_str().length()
+    }
+}
+impl HasLength for Cow<'_, str> {
+    #[inline]
+    fn length(&self) -> usize {
+        self.as_ref().length()
+    }
+}
+impl<T> HasLength for [T] {
+    #[inline]
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+impl<T> HasLength for Vec<T> {
+    #[inline]
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+impl<K, V, S> HasLength for HashMap<K, V, S> {
+    #[inline]
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+impl<K, V> HasLength for BTreeMap<K, V> {
+    #[inline]
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+impl<T: HasLength + ?Sized> HasLength for &T {
+    #[inline]
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+}
+
+/// Types which can be validated by `required` and `required_if` validators.
+pub trait Required {
+    /// Returns true if the value is present, `None` and empty strings or collections are not present.
+    fn is_present(&self) -> bool;
+}
+impl<T> Required for Option<T> {
+    #[inline]
+    fn is_present(&self) -> bool {
+        self.is_some()
+    }
+}
+impl Required for str {
+    #[inline]
+    fn is_present(&self) -> bool {
+        !self.is_empty()
+    }
+}
+impl Required for String {
+    #[inline]
+    fn is_present(&self) -> bool {
+        !self.is_empty()
+    }
+}
+impl<T> Required for Vec<T> {
+    #[inline]
+    fn is_present(&self) -> bool {
+        !self.is_empty()
+    }
+}
+impl<T: Required + ?Sized> Required for &T {
+    #[inline]
+    fn is_present(&self) -> bool {
+        (**self).is_present()
+    }
+}
+
+/// Types which contain uploaded files can be validated by `file` validator.
+pub trait FileParts {
+    /// Get all the files.
+    fn file_parts(&self) -> Vec<&FilePart>;
+}
+impl FileParts for FilePart {
+    #[inline]
+    fn file_parts(&self) -> Vec<&FilePart> {
+        vec![self]
+    }
+}
+impl FileParts for [FilePart] {
+    #[inline]
+    fn file_parts(&self) -> Vec<&FilePart> {
+        self.iter().collect()
+    }
+}
+impl FileParts for Vec<FilePart> {
+    #[inline]
+    fn file_parts(&self) -> Vec<&FilePart> {
+        self.iter().collect()
+    }
+}
+impl<T: FileParts + ?Sized> FileParts for &T {
+    #[inline]
+    fn file_parts(&self) -> Vec<&FilePart> {
+        (**self).file_parts()
+    }
+}
+
+/// Validate the length of value is in range.
+pub fn length<T: HasLength + ?Sized>(value: &T, min: Option<usize>, max: Option<usize>) -> Result<(), String> {
+    let length = value.length();
+    match (min, max) {
+        (Some(min), Some(max)) if length < min || length > max => {
+            Err(format!("length must be between {min} and {max}"))
+        }
+        (Some(min), None) if length < min => Err(format!("length must be at least {min}")),
+        (None, Some(max)) if length > max => Err(format!("length must be at most {max}")),
+        _ => Ok(()),
+    }
+}
+
+/// Validate the value is in range.
+pub fn range<T: PartialOrd + Display>(value: &T, min: Option<T>, max: Option<T>) -> Result<(), String> {
+    match (min, max) {
+        (Some(min), Some(max)) if *value < min || *value > max => Err(format!("must be between {min} and {max}")),
+        (Some(min), None) if *value < min => Err(format!("must be at least {min}")),
+        (None, Some(max)) if *value > max => Err(format!("must be at most {max}")),
+        _ => Ok(()),
+    }
+}
+
+/// Validate the value is matched by the regex.
+pub fn regex<T: AsRef<str> + ?Sized>(value: &T, regex: &Regex) -> Result<(), String> {
+    if regex.is_match(value.as_ref()) {
+        Ok(())
+    } else {
+        Err(format!("must match pattern `{}`", regex.as_str()))
+    }
+}
+
+/// Validate the value is an email address.
+///
+/// Only the basic shape `local@domain` is checked, the domain must contain at least one dot.
+pub fn email<T: AsRef<str> + ?Sized>(value: &T) -> Result<(), String> {
+    let value = value.as_ref();
+    let valid = match value.rsplit_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && local.len() <= 64
+                && !local.chars().any(|ch| ch.is_whitespace() || ch == '@')
+                && domain.split('.').count() > 1
+                && domain
+                    .split('.')
+                    .all(|label| !label.is_empty() && label.chars().all(|ch| ch.is_alphanumeric() || ch == '-'))
+        }
+        None => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err("must be a valid email address".into())
+    }
+}
+
+/// Validate the uploaded files.
+///
+/// `max_size` is the max size in bytes of each file, `max_count` is the max count of files and `content_types`
+/// is the allowed content types, `*` can be used as subtype, such as `image/*`. Empty `content_types` allows all.
+///
+/// The files are checked after they are received, so `max_size` does not limit the upload itself, use
+/// `MaxSize` of `salvo_extra` or [`MultipartLimits`](crate::http::form::MultipartLimits) for that.
+pub fn file<T: FileParts + ?Sized>(
+    value: &T,
+    max_size: Option<u64>,
+    max_count: Option<usize>,
+    content_types: &[&str],
+) -> Result<(), String> {
+    let files = value.file_parts();
+    if let Some(max_count) = max_count {
+        if files.len() > max_count {
+            return Err(format!("file count must be at most {max_count}"));
+        }
+    }
+    for file in files {
+        if let Some(max_size) = max_size {
+            if file.size() > max_size {
+                return Err(format!("file size must be at most {max_size} bytes"));
+            }
+        }
+        if !content_types.is_empty() {
+            let allowed = match file.content_type() {
+                Some(ctype) => content_types.iter().any(|allowed| match allowed.split_once('/') {
+                    Some((type_, "*")) => ctype.type_() == type_,
+                    _ => ctype.essence_str() == *allowed,
+                }),
+                None => false,
+            };
+            if !allowed {
+                return Err(format!("file content type must be one of {}", content_types.join(", ")));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Validate the value is present.
+pub fn required<T: Required + ?Sized>(value: &T) -> Result<(), String> {
+    if value.is_present() {
+        Ok(())
+    } else {
+        Err("is required".into())
+    }
+}
+
+/// Create [`InvalidParam`] for the field of the type, it is used by macros internal.
+#[doc(hidden)]
+pub fn invalid_param(metadata: &Metadata, field_name: &str, reason: String) -> InvalidParam {
+    match metadata.field(field_name) {
+        Some(field) => {
+            let mut param = InvalidParam::new(metadata.request_name(field), reason);
+            param.source = metadata.field_source(field).map(|source| source.from);
+            param
+        }
+        None => InvalidParam::new(field_name, reason),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validators() {
+        assert!(length("abc", Some(3), Some(5)).is_ok());
+        assert!(length("中文", Some(3), None).is_err());
+        assert!(length(&vec![1, 2, 3], None, Some(2)).is_err());
+
+        assert!(range(&5, Some(1), Some(10)).is_ok());
+        assert_eq!(range(&0, Some(1), None).unwrap_err(), "must be at least 1");
+        assert!(range(&1.5, None, Some(1.0)).is_err());
+
+        let pattern = Regex::new("^[a-z]+$").unwrap();
+        assert!(regex("abc", &pattern).is_ok());
+        assert!(regex("ABC", &pattern).is_err());
+
+        assert!(email("chris@salvo.rs").is_ok());
+        assert!(email("chris@localhost").is_err());
+        assert!(email("chris salvo.rs").is_err());
+        assert!(email("@salvo.rs").is_err());
+
+        assert!(required(&Some(1)).is_ok());
+        assert!(required(&None::<i32>).is_err());
+        assert!(required("").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_validate_extractible() {
+        use serde::Deserialize;
+
+        use crate::http::ParseError;
+        use crate::prelude::*;
+        use crate::test::{ResponseExt, TestClient};
+
+        #[derive(Deserialize, Extractible, Debug)]
+        #[extract(default_source(from = "query"))]
+        struct Address {
+            #[extract(validate(length(min = 2)))]
+            city: String,
+        }
+        #[derive(Deserialize, Extractible, Debug)]
+        #[extract(default_source(from = "query"), rename_all = "camelCase")]
+        struct Register {
+            #[extract(validate(length(min = 3, max = 20), regex = "^[a-z0-9_]+$"))]
+            user_name: String,
+            #[extract(source(from = "header"), rename = "x-email", validate(email))]
+            email: String,
+            #[extract(validate(range(min = 18, max = 150)))]
+            age: Option<u8>,
+            kind: String,
+            #[extract(validate(required_if(field = "kind", value = "company")))]
+            company: Option<String>,
+            #[extract(source(from = "request"))]
+            address: Address,
+        }
+        #[handler]
+        async fn register(data: Register) -> String {
+            format!("{data:?}")
+        }
+        let service = Service::new(Router::new().post(register));
+
+        let mut res = TestClient::post("http://127.0.0.1:5800/?userName=chris&age=20&kind=person&city=sh")
+            .add_header("x-email", "chris@salvo.rs", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert!(res.take_string().await.unwrap().contains("chris@salvo.rs"));
+
+        let mut res = TestClient::post("http://127.0.0.1:5800/?userName=Ch&age=3&kind=company&city=s")
+            .add_header("x-email", "chris", true)
+            .add_header("accept", "application/problem+json", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::UNPROCESSABLE_ENTITY));
+        let problem: serde_json::Value = res.take_json().await.unwrap();
+        let params = problem["invalid-params"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|param| {
+                (
+                    param["name"].as_str().unwrap().to_owned(),
+                    param["source"].as_str().unwrap().to_owned(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            params,
+            [
+                ("userName", "query"),
+                ("userName", "query"),
+                ("x-email", "header"),
+                ("age", "query"),
+                ("company", "query"),
+                ("city", "query"),
+            ]
+            .map(|(name, source)| (name.to_owned(), source.to_owned()))
+        );
+
+        let mut req = TestClient::get("http://127.0.0.1:5800/?city=s").build();
+        match req.extract::<Address>().await {
+            Err(ParseError::Validation(params)) => assert_eq!(params[0].name, "city"),
+            result => panic!("unexpected result: {result:?}"),
+        }
+        let mut req = TestClient::get("http://127.0.0.1:5800/?city=sh").build();
+        assert_eq!(req.extract::<Address>().await.unwrap().city, "sh");
+    }
+
+    #[tokio::test]
+    async fn test_validate_files() {
+        use serde::Deserialize;
+
+        use crate::http::form::FilePart;
+        use crate::prelude::*;
+        use crate::test::{ResponseExt, TestClient};
+
+        #[derive(Deserialize, Extractible, Debug)]
+        #[extract(default_source(from = "body"))]
+        struct Upload {
+            #[extract(validate(file(max_size = 8, content_types = ["image/*"])))]
+            cover: Option<FilePart>,
+            #[extract(validate(file(max_count = 2, content_types = ["text/plain"])))]
+            attachments: Vec<FilePart>,
+        }
+        #[handler]
+        async fn upload(data: Upload) -> String {
+            format!("{} {}", data.cover.is_some(), data.attachments.len())
+        }
+        let service = Service::new(Router::new().post(upload));
+
+        async fn access(service: &Service, parts: &[(&str, &str, &str)]) -> (StatusCode, String) {
+            let mut body = String::new();
+            for (name, ctype, content) in parts {
+                body.push_str(&format!(
+                    "--boundary\r\nContent-Disposition: form-data; name=\"{name}\"; filename=\"{name}\"\r\n\
+                    Content-Type: {ctype}\r\n\r\n{content}\r\n"
+                ));
+            }
+            body.push_str("--boundary--\r\n");
+            let mut res = TestClient::post("http://127.0.0.1:5800/")
+                .add_header("content-type", "multipart/form-data; boundary=boundary", true)
+                .add_header("accept", "application/problem+json", true)
+                .body(body)
+                .send(service)
+                .await;
+            (res.status_code.unwrap(), res.take_string().await.unwrap())
+        }
+
+        let attachment = ("attachments", "text/plain", "a");
+        assert_eq!(
+            access(&service, &[("cover", "image/png", "png"), attachment, attachment]).await,
+            (StatusCode::OK, "true 2".to_owned())
+        );
+        let (status, body) = access(&service, &[("cover", "image/png", "too large png"), attachment]).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(body.contains("file size must be at most 8 bytes"));
+        let (status, body) = access(&service, &[("cover", "text/plain", "png"), attachment]).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(body.contains("file content type must be one of image/*"));
+        let (status, body) = access(&service, &[attachment, attachment, attachment]).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(body.contains("file count must be at most 2"));
+    }
+}
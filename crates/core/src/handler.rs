@@ -48,6 +48,10 @@ pub trait Handler: Send + Sync + 'static {
     fn type_name(&self) -> &'static str {
         std::any::type_name::<Self>()
     }
+    #[doc(hidden)]
+    fn as_mounted_service(&self) -> Option<&crate::Service> {
+        None
+    }
     /// Handle http request.
     #[must_use = "handle future must be used"]
     async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl);
@@ -1,5 +1,5 @@
 //! Http body.
-pub use hyper::body::{Body, SizeHint};
+pub use hyper::body::{Body, Frame, SizeHint};
 
 mod req;
 #[cfg(feature = "quinn")]
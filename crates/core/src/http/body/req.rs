@@ -180,11 +180,14 @@ cfg_feature! {
                 let this = &mut *self;
                 let rt = tokio::runtime::Runtime::new().unwrap();
                 // TODO: how to remove block?
-                Poll::Ready(Some(rt.block_on(async move {
-                    let buf = this.inner.recv_data().await.unwrap();
-                    let buf = buf.map(|buf| Bytes::copy_from_slice(buf.chunk()));
-                    Ok(Frame::data(buf.unwrap()))
-                })))
+                let frame = rt.block_on(async move {
+                    match this.inner.recv_data().await? {
+                        Some(mut buf) => Ok(Some(Frame::data(buf.copy_to_bytes(buf.remaining())))),
+                        // Trailers are received after all the data.
+                        None => Ok(this.inner.recv_trailers().await?.map(Frame::trailers)),
+                    }
+                });
+                Poll::Ready(frame.transpose())
             }
 
             fn is_end_stream(&self) -> bool {
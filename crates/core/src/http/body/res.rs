@@ -6,7 +6,9 @@ use std::io::{Error as IoError, ErrorKind};
 use std::pin::Pin;
 use std::task::{self, Context, Poll};
 
-use futures_util::stream::{BoxStream, Stream};
+use futures_util::future;
+use futures_util::stream::{BoxStream, Stream, StreamExt};
+use http::header::HeaderMap;
 use hyper::body::{Body, Frame, Incoming, SizeHint};
 
 use bytes::Bytes;
@@ -28,6 +30,8 @@ pub enum ResBody {
     Hyper(Incoming),
     /// Stream body.
     Stream(BoxStream<'static, Result<Bytes, BoxedError>>),
+    /// Stream body of frames, trailers frame can be yielded after data frames.
+    Frames(BoxStream<'static, Result<Frame<Bytes>, BoxedError>>),
     /// Error body will be process in catcher.
     Error(StatusError),
 }
@@ -52,6 +56,11 @@ impl ResBody {
     pub fn is_stream(&self) -> bool {
         matches!(*self, ResBody::Stream(_))
     }
+    /// Check is that body is stream of frames.
+    #[inline]
+    pub fn is_frames(&self) -> bool {
+        matches!(*self, ResBody::Frames(_))
+    }
     /// Check is that body is error will be process in catcher.
     pub fn is_error(&self) -> bool {
         matches!(*self, ResBody::Error(_))
@@ -65,9 +74,66 @@ impl ResBody {
             ResBody::Chunks(chunks) => Some(chunks.iter().map(|bytes| bytes.len() as u64).sum()),
             ResBody::Hyper(_) => None,
             ResBody::Stream(_) => None,
+            ResBody::Frames(_) => None,
             ResBody::Error(_) => None,
         }
     }
+
+    /// Send the trailers after all frames of this body, they are merged with the trailers sent by this body.
+    pub(crate) fn with_trailers(self, trailers: HeaderMap) -> ResBody {
+        ResBody::Frames(Box::pin(TrailersStream {
+            body: self,
+            trailers: Some(trailers),
+        }))
+    }
+
+    /// Drop the trailers frames of this body with a warning, they can not be sent over HTTP/1.
+    pub(crate) fn discard_trailers(self) -> ResBody {
+        match self {
+            ResBody::Frames(frames) => ResBody::Frames(Box::pin(frames.filter(|frame| {
+                let is_trailers = matches!(frame, Ok(frame) if frame.is_trailers());
+                if is_trailers {
+                    tracing::warn!("response trailers are only sent over HTTP/2 and HTTP/3, discarded");
+                }
+                future::ready(!is_trailers)
+            }))),
+            body => body,
+        }
+    }
+}
+
+/// Stream yields frames of the body and then the trailers.
+struct TrailersStream {
+    body: ResBody,
+    trailers: Option<HeaderMap>,
+}
+impl Stream for TrailersStream {
+    type Item = Result<Frame<Bytes>, BoxedError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let trailers = match this.trailers.as_mut() {
+                Some(trailers) => trailers,
+                None => return Poll::Ready(None),
+            };
+            match Body::poll_frame(Pin::new(&mut this.body), cx) {
+                Poll::Ready(Some(Ok(frame))) => match frame.into_trailers() {
+                    Ok(body_trailers) => {
+                        for (name, value) in body_trailers.iter() {
+                            trailers.append(name, value.clone());
+                        }
+                    }
+                    Err(frame) => return Poll::Ready(Some(Ok(frame))),
+                },
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e.into()))),
+                Poll::Ready(None) => {
+                    return Poll::Ready(this.trailers.take().map(|trailers| Ok(Frame::trailers(trailers))))
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
 }
 
 impl Stream for ResBody {
@@ -96,6 +162,19 @@ impl Stream for ResBody {
                 .as_mut()
                 .poll_next(cx)
                 .map_err(|e| IoError::new(ErrorKind::Other, e)),
+            ResBody::Frames(stream) => loop {
+                match stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(Ok(frame))) => {
+                        // Trailers are skipped when the body is used as a stream of bytes.
+                        if let Ok(bytes) = frame.into_data() {
+                            return Poll::Ready(Some(Ok(bytes)));
+                        }
+                    }
+                    Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(IoError::new(ErrorKind::Other, e)))),
+                    Poll::Ready(None) => return Poll::Ready(None),
+                    Poll::Pending => return Poll::Pending,
+                }
+            },
             ResBody::Error(_) => Poll::Ready(None),
         }
     }
@@ -107,13 +186,21 @@ impl Body for ResBody {
 
     fn poll_frame(
         self: Pin<&mut Self>,
-        _cx: &mut Context<'_>,
+        cx: &mut Context<'_>,
     ) -> Poll<Option<Result<Frame<Self::Data>, <ResBody as Body>::Error>>> {
-        match self.poll_next(_cx) {
-            Poll::Ready(Some(Ok(bytes))) => Poll::Ready(Some(Ok(Frame::data(bytes)))),
-            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
-            Poll::Ready(None) => Poll::Ready(None),
-            Poll::Pending => Poll::Pending,
+        match self.get_mut() {
+            // Trailers frames are only kept by the bodies which are based on frames.
+            ResBody::Hyper(body) => Body::poll_frame(Pin::new(body), cx).map_err(|e| IoError::new(ErrorKind::Other, e)),
+            ResBody::Frames(stream) => stream
+                .as_mut()
+                .poll_next(cx)
+                .map_err(|e| IoError::new(ErrorKind::Other, e)),
+            body => match Pin::new(body).poll_next(cx) {
+                Poll::Ready(Some(Ok(bytes))) => Poll::Ready(Some(Ok(Frame::data(bytes)))),
+                Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => Poll::Ready(None),
+                Poll::Pending => Poll::Pending,
+            },
         }
     }
 
@@ -124,6 +211,7 @@ impl Body for ResBody {
             ResBody::Chunks(chunks) => chunks.is_empty(),
             ResBody::Hyper(body) => body.is_end_stream(),
             ResBody::Stream(_) => false,
+            ResBody::Frames(_) => false,
             ResBody::Error(_) => true,
         }
     }
@@ -138,6 +226,7 @@ impl Body for ResBody {
             }
             ResBody::Hyper(recv) => recv.size_hint(),
             ResBody::Stream(_) => SizeHint::default(),
+            ResBody::Frames(_) => SizeHint::default(),
             ResBody::Error(_) => SizeHint::with_exact(0),
         }
     }
@@ -2,5 +2,5 @@
 
 mod parse_error;
 mod status_error;
-pub use parse_error::{ParseError, ParseResult};
+pub use parse_error::{InvalidParam, ParseError, ParseResult};
 pub use status_error::{StatusError, StatusResult};
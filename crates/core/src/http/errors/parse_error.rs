@@ -4,6 +4,7 @@ use std::str::Utf8Error;
 use serde::de::value::Error as DeError;
 use thiserror::Error;
 
+use crate::extract::metadata::SourceFrom;
 use crate::http::StatusError;
 use crate::{BoxedError, Piece, Response};
 
@@ -34,6 +35,22 @@ pub enum ParseError {
     #[error("Deserialize error.")]
     Deserialize(#[from] DeError),
 
+    /// Validation error of the extracted data, with all the invalid fields.
+    #[error("Validation error.")]
+    Validation(Vec<InvalidParam>),
+
+    /// Deserialize error when extract data from request, with the fields which are failed to parse.
+    ///
+    /// Errors without any field information are still [`ParseError::Deserialize`].
+    #[error("Extract error: {source}")]
+    #[non_exhaustive]
+    Extract {
+        /// The original deserialize error.
+        source: DeError,
+        /// The fields which are failed to parse.
+        params: Vec<InvalidParam>,
+    },
+
     /// DuplicateKey.
     #[error("DuplicateKey.")]
     DuplicateKey,
@@ -70,16 +87,79 @@ pub enum ParseError {
     #[error("Serde json error: {0}")]
     SerdeJson(#[from] serde_json::error::Error),
 
+    /// Serde msgpack error.
+    #[cfg(feature = "msgpack")]
+    #[error("Serde msgpack error: {0}")]
+    SerdeMsgPack(#[from] rmp_serde::decode::Error),
+
+    /// Serde cbor error.
+    #[cfg(feature = "cbor")]
+    #[error("Serde cbor error: {0}")]
+    SerdeCbor(#[from] ciborium::de::Error<std::io::Error>),
+
+    /// Serde xml error.
+    #[cfg(feature = "xml")]
+    #[error("Serde xml error: {0}")]
+    SerdeXml(#[from] quick_xml::DeError),
+
     /// Custom error that does not fall under any other error kind.
     #[error("Other error: {0}")]
     Other(BoxedError),
 }
+/// A field of request which is failed to parse, it is an item of `invalid-params` in problem details.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidParam {
+    /// Name of the field.
+    pub name: String,
+    /// Reason why the field is invalid.
+    pub reason: String,
+    /// Where the field is extracted from.
+    pub source: Option<SourceFrom>,
+}
+impl InvalidParam {
+    /// Create new `InvalidParam`.
+    #[inline]
+    pub fn new(name: impl Into<String>, reason: impl Into<String>) -> Self {
+        InvalidParam {
+            name: name.into(),
+            reason: reason.into(),
+            source: None,
+        }
+    }
+    /// Sets source field and returns Self.
+    #[inline]
+    pub fn source(mut self, source: SourceFrom) -> Self {
+        self.source = Some(source);
+        self
+    }
+}
+
 impl ParseError {
     /// Create a custom error.
     #[inline]
     pub fn other(error: impl Into<BoxedError>) -> Self {
         Self::Other(error.into())
     }
+    /// Get the fields which are failed to parse, it is empty if the error is not caused by fields.
+    #[inline]
+    pub fn invalid_params(&self) -> Vec<InvalidParam> {
+        match self {
+            Self::Extract { params, .. } | Self::Validation(params) => params.clone(),
+            _ => vec![],
+        }
+    }
+    /// Convert to [`StatusError`] which is rendered when extract data from request failed.
+    ///
+    /// `422 Unprocessable Entity` is used for validation errors and `400 Bad Request` for others,
+    /// the invalid fields are kept in `invalid-params` member of problem details.
+    pub fn into_status_error(self) -> StatusError {
+        let err = if let Self::Validation(_) = self {
+            StatusError::unprocessable_entity().brief("Validate data failed.")
+        } else {
+            StatusError::bad_request().brief("Extract data failed.")
+        };
+        err.invalid_params(self.invalid_params()).cause(self)
+    }
 }
 
 impl Piece for ParseError {
@@ -1,6 +1,9 @@
 use std::error::Error as StdError;
 use std::fmt::{self, Debug, Display, Formatter};
 
+use serde_json::{Map, Value};
+
+use crate::http::errors::InvalidParam;
 use crate::http::{ResBody, StatusCode};
 
 use crate::{Piece, Response};
@@ -18,6 +21,7 @@ macro_rules! default_errors {
                     name: $name.into(),
                     brief: $brief.into(),
                     cause: None,
+                    extensions: Map::new(),
                 }
             }
         )+
@@ -35,6 +39,7 @@ pub struct StatusError {
     pub brief: String,
     /// Cause about http error. This field is only used for internal debugging and only used in debug mode.
     pub cause: Option<Box<dyn StdError + Sync + Send + 'static>>,
+    extensions: Map<String, Value>,
 }
 impl StatusError {
     /// Sets brief field and returns Self.
@@ -50,6 +55,44 @@ impl StatusError {
         self.cause = Some(cause.into());
         self
     }
+    /// Sets `type` member which identifies the problem type and returns Self.
+    pub fn type_uri(self, type_uri: impl Into<String>) -> Self {
+        self.extension("type", type_uri.into())
+    }
+    /// Sets `instance` member which identifies the specific occurrence of the problem and returns Self.
+    pub fn instance(self, instance: impl Into<String>) -> Self {
+        self.extension("instance", instance.into())
+    }
+    /// Get members of problem details (RFC 7807) other than `title`, `status` and `detail`, which come from
+    /// `name`, `code` and `brief`. The `type` and `instance` members are also kept here.
+    #[inline]
+    pub fn extensions(&self) -> &Map<String, Value> {
+        &self.extensions
+    }
+    /// Adds an extension member and returns Self.
+    pub fn extension(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.extensions.insert(name.into(), value.into());
+        self
+    }
+    /// Sets `invalid-params` extension member and returns Self, nothing is changed if `params` is empty.
+    pub fn invalid_params(self, params: Vec<InvalidParam>) -> Self {
+        if params.is_empty() {
+            return self;
+        }
+        let params = params
+            .into_iter()
+            .map(|param| {
+                let mut item = Map::new();
+                item.insert("name".into(), param.name.into());
+                item.insert("reason".into(), param.reason.into());
+                if let Some(source) = param.source {
+                    item.insert("source".into(), source.as_str().into());
+                }
+                Value::Object(item)
+            })
+            .collect::<Vec<_>>();
+        self.extension("invalid-params", params)
+    }
 
     default_errors! {
         bad_request,                        StatusCode::BAD_REQUEST,            "Bad Request", "The request could not be understood by the server due to malformed syntax.";
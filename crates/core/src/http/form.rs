@@ -1,10 +1,20 @@
 //! form parse module
+use std::cell::RefCell;
 use std::ffi::OsStr;
+use std::fmt::{self, Formatter};
+use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
 
+use bytes::Bytes;
+use futures_util::stream::Stream;
 use http_body_util::BodyExt;
-use multer::{Field, Multipart};
+use multer::{Constraints, Field, Multipart, SizeLimit};
 use multimap::MultiMap;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
 use tempfile::Builder;
 use textnonce::TextNonce;
 use tokio::fs::File;
@@ -12,7 +22,7 @@ use tokio::io::AsyncWriteExt;
 
 use crate::http::body::ReqBody;
 use crate::http::header::{HeaderMap, CONTENT_TYPE};
-use crate::http::ParseError;
+use crate::http::{Mime, ParseError};
 
 /// The extracted text fields and uploaded files from a `multipart/form-data` request.
 #[derive(Debug)]
@@ -74,6 +84,132 @@ impl Default for FormData {
         Self::new()
     }
 }
+/// Size limits of [`MultipartStream`].
+///
+/// Exceeding any of the limits makes the stream returns [`ParseError::Multer`].
+#[derive(Clone, Copy, Default, Debug)]
+pub struct MultipartLimits {
+    per_part: Option<u64>,
+    total: Option<u64>,
+}
+impl MultipartLimits {
+    /// Create new `MultipartLimits` without any limit.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Sets the max size in bytes of each part.
+    #[inline]
+    pub fn per_part(mut self, size: u64) -> Self {
+        self.per_part = Some(size);
+        self
+    }
+    /// Sets the max size in bytes of the whole multipart body.
+    #[inline]
+    pub fn total(mut self, size: u64) -> Self {
+        self.total = Some(size);
+        self
+    }
+    fn constraints(&self) -> Constraints {
+        let mut size_limit = SizeLimit::new();
+        if let Some(size) = self.per_part {
+            size_limit = size_limit.per_field(size);
+        }
+        if let Some(size) = self.total {
+            size_limit = size_limit.whole_stream(size);
+        }
+        Constraints::new().size_limit(size_limit)
+    }
+}
+
+/// Streaming parser of `multipart/*` request body, it yields parts one at a time without buffering them.
+///
+/// It is created by [`Request::multipart_stream`](crate::http::Request::multipart_stream).
+pub struct MultipartStream {
+    inner: Multipart<'static>,
+}
+impl fmt::Debug for MultipartStream {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("MultipartStream").finish()
+    }
+}
+impl MultipartStream {
+    /// Parse MIME `multipart/*` information from body with the given limits.
+    pub(crate) fn new(headers: &HeaderMap, body: ReqBody, limits: MultipartLimits) -> Result<Self, ParseError> {
+        let boundary = headers
+            .get(CONTENT_TYPE)
+            .and_then(|ct| ct.to_str().ok())
+            .ok_or(ParseError::NotMultipart)
+            .and_then(|ct| multer::parse_boundary(ct).map_err(ParseError::from))?;
+        Ok(Self {
+            inner: Multipart::with_constraints(body, boundary, limits.constraints()),
+        })
+    }
+
+    /// Get the next part, returns `None` if there are no more parts.
+    ///
+    /// The previous part must be dropped before calling this, the rest content of it is skipped.
+    #[inline]
+    pub async fn next_part(&mut self) -> Result<Option<MultipartPart<'_>>, ParseError> {
+        Ok(self.inner.next_field().await?.map(|inner| MultipartPart {
+            inner,
+            _stream: PhantomData,
+        }))
+    }
+}
+
+/// A part of [`MultipartStream`], it's content is a stream of bytes.
+#[derive(Debug)]
+pub struct MultipartPart<'a> {
+    inner: Field<'static>,
+    _stream: PhantomData<&'a mut MultipartStream>,
+}
+impl MultipartPart<'_> {
+    /// Get the name of this part.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.inner.name()
+    }
+    /// Get the file name of this part, it is `None` for plain text fields.
+    #[inline]
+    pub fn file_name(&self) -> Option<&str> {
+        self.inner.file_name()
+    }
+    /// Get content type of this part.
+    #[inline]
+    pub fn content_type(&self) -> Option<&Mime> {
+        self.inner.content_type()
+    }
+    /// Get headers of this part.
+    #[inline]
+    pub fn headers(&self) -> &HeaderMap {
+        self.inner.headers()
+    }
+    /// Get the next chunk of content, returns `None` if the content is read completely.
+    #[inline]
+    pub async fn chunk(&mut self) -> Result<Option<Bytes>, ParseError> {
+        Ok(self.inner.chunk().await?)
+    }
+    /// Read the whole content as bytes.
+    #[inline]
+    pub async fn bytes(self) -> Result<Bytes, ParseError> {
+        Ok(self.inner.bytes().await?)
+    }
+    /// Read the whole content as text.
+    #[inline]
+    pub async fn text(self) -> Result<String, ParseError> {
+        Ok(self.inner.text().await?)
+    }
+}
+impl Stream for MultipartPart<'_> {
+    type Item = Result<Bytes, ParseError>;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(cx).map_err(ParseError::from)
+    }
+}
+
 /// A file that is to be inserted into a `multipart/*` or alternatively an uploaded file that
 /// was received as part of `multipart/*` parsing.
 #[derive(Clone, Debug)]
@@ -86,8 +222,8 @@ pub struct FilePart {
     /// Optionally, the size of the file.  This is filled when multiparts are parsed, but is
     /// not necessary when they are generated.
     size: u64,
-    // The temporary directory the upload was put into, saved for the Drop trait
-    temp_dir: Option<PathBuf>,
+    // The temporary file, it is shared by the clones and deleted when the last one drops.
+    temp_file: Option<Arc<TempFile>>,
 }
 impl FilePart {
     /// Get file name.
@@ -109,6 +245,14 @@ impl FilePart {
     pub fn headers_mut(&mut self) -> &mut HeaderMap {
         &mut self.headers
     }
+    /// Get content type of this file, it is parsed from the `Content-Type` header of the part.
+    #[inline]
+    pub fn content_type(&self) -> Option<Mime> {
+        self.headers
+            .get(CONTENT_TYPE)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|v| v.parse().ok())
+    }
     /// Get file path.
     #[inline]
     pub fn path(&self) -> &PathBuf {
@@ -123,7 +267,9 @@ impl FilePart {
     /// function.  It will become your responsibility to clean up.
     #[inline]
     pub fn do_not_delete_on_drop(&mut self) {
-        self.temp_dir = None;
+        if let Some(temp_file) = self.temp_file.take() {
+            temp_file.keep.store(true, Ordering::Release);
+        }
     }
 
     /// Create a new temporary FilePart (when created this way, the file will be
@@ -135,7 +281,7 @@ impl FilePart {
             .await
             .expect("Runtime spawn blocking poll error")?
             .into_path();
-        let temp_dir = Some(path.clone());
+        let temp_dir = path.clone();
         let name = field.file_name().map(|s| s.to_owned());
         path.push(format!(
             "{}.{}",
@@ -153,21 +299,88 @@ impl FilePart {
         Ok(FilePart {
             name,
             headers: field.headers().to_owned(),
+            temp_file: Some(Arc::new(TempFile {
+                path: path.clone(),
+                dir: temp_dir,
+                keep: AtomicBool::new(false),
+            })),
             path,
             size,
-            temp_dir,
         })
     }
 }
-impl Drop for FilePart {
+
+/// A temporary file and the directory it was put into, both are deleted on drop.
+#[derive(Debug)]
+struct TempFile {
+    path: PathBuf,
+    dir: PathBuf,
+    keep: AtomicBool,
+}
+impl Drop for TempFile {
     fn drop(&mut self) {
-        if let Some(temp_dir) = &self.temp_dir {
+        if !self.keep.load(Ordering::Acquire) {
             let path = self.path.clone();
-            let temp_dir = temp_dir.to_owned();
+            let dir = self.dir.clone();
             tokio::task::spawn_blocking(move || {
                 std::fs::remove_file(&path).ok();
-                std::fs::remove_dir(temp_dir).ok();
+                std::fs::remove_dir(dir).ok();
             });
         }
     }
 }
+
+/// The name of the newtype struct [`FilePart`] is deserialized from.
+pub(crate) const FILE_PART_NAME: &str = "$salvo::FilePart";
+
+thread_local! {
+    /// The file part handed to [`FilePart`]'s `Deserialize` implementation, it is only set while the request
+    /// deserializer visits a file of the current request.
+    static DESERIALIZING_FILE: RefCell<Option<FilePart>> = const { RefCell::new(None) };
+}
+
+/// Call `f` with `file` available to [`FilePart`]'s `Deserialize` implementation, the file is cleared when `f`
+/// returns or panics.
+pub(crate) fn with_deserializing_file<R>(file: &FilePart, f: impl FnOnce() -> R) -> R {
+    struct Guard(Option<FilePart>);
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            DESERIALIZING_FILE.with(|slot| *slot.borrow_mut() = previous);
+        }
+    }
+    let previous = DESERIALIZING_FILE.with(|slot| slot.borrow_mut().replace(file.clone()));
+    let _guard = Guard(previous);
+    f()
+}
+
+/// `FilePart` can only be deserialized from the files of a `multipart/form-data` request by `Extractible` types,
+/// deserializing it from any other data always fails.
+///
+/// The deserialized `FilePart` is a clone of the one in request's [`FormData`], the file is deleted when all of
+/// them drop.
+impl<'de> Deserialize<'de> for FilePart {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FilePartVisitor;
+        impl<'de> Visitor<'de> for FilePartVisitor {
+            type Value = FilePart;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str("a file of multipart form")
+            }
+
+            fn visit_newtype_struct<D>(self, _deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                DESERIALIZING_FILE
+                    .with(|slot| slot.borrow_mut().take())
+                    .ok_or_else(|| de::Error::custom("file part can only be extracted from multipart form"))
+            }
+        }
+        deserializer.deserialize_newtype_struct(FILE_PART_NAME, FilePartVisitor)
+    }
+}
@@ -25,6 +25,8 @@ pub use http::version::Version;
 use std::io::Result as IoResult;
 use std::sync::Arc;
 
+use tokio_util::sync::CancellationToken;
+
 use crate::async_trait;
 use crate::conn::HttpBuilders;
 use crate::service::HyperHandler;
@@ -35,7 +37,15 @@ pub trait HttpConnection {
     /// The http protocol version.
     async fn version(&mut self) -> Option<Version>;
     /// Serve this http connection.
-    async fn serve(self, handler: HyperHandler, builders: Arc<HttpBuilders>) -> IoResult<()>;
+    ///
+    /// When `graceful_stop_token` is cancelled, the connection should stop taking new requests and finish once the
+    /// in-flight ones are done.
+    async fn serve(
+        self,
+        handler: HyperHandler,
+        builders: Arc<HttpBuilders>,
+        graceful_stop_token: CancellationToken,
+    ) -> IoResult<()>;
 }
 
 /// Get Http version from alph.
@@ -1,10 +1,12 @@
 //! Http request.
 
 use std::fmt::{self, Formatter};
+#[cfg(feature = "cookie")]
+use std::sync::Arc;
 
 use bytes::Bytes;
 #[cfg(feature = "cookie")]
-use cookie::{Cookie, CookieJar};
+use cookie::{Cookie, CookieJar, Key};
 use http::header::{AsHeaderName, HeaderMap, HeaderValue, IntoHeaderName};
 use http::method::Method;
 pub use http::request::Parts;
@@ -21,7 +23,7 @@ use serde::de::Deserialize;
 use crate::conn::SocketAddr;
 use crate::extract::{Extractible, Metadata};
 use crate::http::body::ReqBody;
-use crate::http::form::{FilePart, FormData};
+use crate::http::form::{FilePart, FormData, MultipartLimits, MultipartStream};
 use crate::http::{Mime, ParseError, Version};
 use crate::serde::{from_request, from_str_map, from_str_multi_map, from_str_multi_val, from_str_val};
 use crate::Error;
@@ -58,6 +60,8 @@ pub struct Request {
 
     #[cfg(feature = "cookie")]
     pub(crate) cookies: CookieJar,
+    #[cfg(feature = "cookie")]
+    pub(crate) cookie_key: Option<Arc<Key>>,
 
     pub(crate) params: IndexMap<String, String>,
 
@@ -65,12 +69,14 @@ pub struct Request {
     pub(crate) queries: OnceCell<MultiMap<String, String>>,
     pub(crate) form_data: tokio::sync::OnceCell<FormData>,
     pub(crate) payload: tokio::sync::OnceCell<Bytes>,
+    pub(crate) trailers: Option<HeaderMap>,
 
     /// The version of the HTTP protocol used.
     pub(crate) version: Version,
     pub(crate) scheme: Scheme,
     pub(crate) local_addr: SocketAddr,
     pub(crate) remote_addr: SocketAddr,
+    pub(crate) mount_prefix: String,
 }
 
 impl fmt::Debug for Request {
@@ -108,14 +114,18 @@ impl Request {
             method: Method::default(),
             #[cfg(feature = "cookie")]
             cookies: CookieJar::default(),
+            #[cfg(feature = "cookie")]
+            cookie_key: None,
             params: IndexMap::new(),
             queries: OnceCell::new(),
             form_data: tokio::sync::OnceCell::new(),
             payload: tokio::sync::OnceCell::new(),
+            trailers: None,
             version: Version::default(),
             scheme: Scheme::HTTP,
             local_addr: SocketAddr::Unknown,
             remote_addr: SocketAddr::Unknown,
+            mount_prefix: String::new(),
         }
     }
     /// Creates a new `Request` from [`hyper::Request`].
@@ -160,13 +170,17 @@ impl Request {
             method,
             #[cfg(feature = "cookie")]
             cookies,
+            #[cfg(feature = "cookie")]
+            cookie_key: None,
             // accept: None,
             params: IndexMap::new(),
             form_data: tokio::sync::OnceCell::new(),
             payload: tokio::sync::OnceCell::new(),
+            trailers: None,
             // multipart: OnceCell::new(),
             local_addr: SocketAddr::Unknown,
             remote_addr: SocketAddr::Unknown,
+            mount_prefix: String::new(),
             version,
             scheme,
         }
@@ -251,6 +265,15 @@ impl Request {
         &mut self.scheme
     }
 
+    /// Get the path prefix where current service is mounted, see [`Router::mount`](crate::routing::Router::mount).
+    ///
+    /// The prefix is already stripped from [`Request::uri`] in the mounted service, it is empty if
+    /// the request is not handled by a mounted service.
+    #[inline]
+    pub fn mount_prefix(&self) -> &str {
+        &self.mount_prefix
+    }
+
     /// Get request remote address.
     #[inline]
     pub fn remote_addr(&self) -> &SocketAddr {
@@ -350,6 +373,15 @@ impl Request {
         self.replace_body(ReqBody::None)
     }
 
+    /// Returns a reference to the trailers received after the body.
+    ///
+    /// Trailers are available after the body is read by [`payload`](Self::payload) or the methods based on it,
+    /// such as [`parse_json`](Self::parse_json).
+    #[inline]
+    pub fn trailers(&self) -> Option<&HeaderMap> {
+        self.trailers.as_ref()
+    }
+
     /// Returns a reference to the associated extensions.
     ///
     /// # Examples
@@ -434,6 +466,38 @@ impl Request {
         {
             self.cookies.get(name.as_ref())
         }
+        /// Get the `Key` used to verify signed cookies and decrypt private cookies.
+        ///
+        /// It is set by [`Service::cookie_key`](crate::Service::cookie_key) or [`Request::set_cookie_key`].
+        #[inline]
+        pub fn cookie_key(&self) -> Option<&Key> {
+            self.cookie_key.as_deref()
+        }
+        /// Set the `Key` used to verify signed cookies and decrypt private cookies.
+        #[inline]
+        pub fn set_cookie_key(&mut self, key: impl Into<Arc<Key>>) {
+            self.cookie_key = Some(key.into());
+        }
+        /// Get signed `Cookie` from cookies, the cookie is returned only if it's signature is verified.
+        ///
+        /// Returns `None` if cookie key is not set.
+        #[inline]
+        pub fn signed_cookie<T>(&self, name: T) -> Option<Cookie<'static>>
+        where
+            T: AsRef<str>,
+        {
+            self.cookie_key().and_then(|key| self.cookies.signed(key).get(name.as_ref()))
+        }
+        /// Get private `Cookie` from cookies, the cookie is returned only if it is decrypted successfully.
+        ///
+        /// Returns `None` if cookie key is not set.
+        #[inline]
+        pub fn private_cookie<T>(&self, name: T) -> Option<Cookie<'static>>
+        where
+            T: AsRef<str>,
+        {
+            self.cookie_key().and_then(|key| self.cookies.private(key).get(name.as_ref()))
+        }
     }
     /// Get params reference.
     #[inline]
@@ -561,13 +625,15 @@ impl Request {
     #[inline]
     pub async fn payload_with_max_size(&mut self, max_size: usize) -> Result<&Bytes, ParseError> {
         let body = self.take_body();
+        let trailers = &mut self.trailers;
         self.payload
             .get_or_try_init(|| async {
-                Ok(Limited::new(body, max_size)
+                let collected = Limited::new(body, max_size)
                     .collect()
                     .await
-                    .map_err(ParseError::other)?
-                    .to_bytes())
+                    .map_err(ParseError::other)?;
+                *trailers = collected.trailers().cloned();
+                Ok(collected.to_bytes())
             })
             .await
     }
@@ -592,13 +658,48 @@ impl Request {
         }
     }
 
-    /// Extract request as type `T` from request's different parts.
+    /// Get a streaming parser of `multipart/*` body, parts are yielded one at a time and their content is not
+    /// buffered into memory or temporary files.
+    ///
+    /// *Notice: This method takes body, so [`Request::form_data`] can not be used after it is called, and it can
+    /// not be used if the body is already taken by other methods.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// use salvo_core::http::form::MultipartLimits;
+    ///
+    /// #[handler]
+    /// async fn upload(req: &mut Request) -> Result<(), salvo_core::http::ParseError> {
+    ///     let limits = MultipartLimits::new().per_part(1024 * 1024 * 1024).total(4 * 1024 * 1024 * 1024);
+    ///     let mut parts = req.multipart_stream(limits)?;
+    ///     while let Some(mut part) = parts.next_part().await? {
+    ///         while let Some(_chunk) = part.chunk().await? {
+    ///             // Write chunk to the storage.
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[inline]
+    pub fn multipart_stream(&mut self, limits: MultipartLimits) -> Result<MultipartStream, ParseError> {
+        match self.content_type() {
+            Some(ctype) if ctype.type_() == mime::MULTIPART => {
+                let body = self.take_body();
+                MultipartStream::new(self.headers(), body, limits)
+            }
+            _ => Err(ParseError::NotMultipart),
+        }
+    }
+
+    /// Extract request as type `T` from request's different parts, the extracted data is validated before returned.
     #[inline]
     pub async fn extract<'de, T>(&'de mut self) -> Result<T, ParseError>
     where
         T: Extractible<'de> + Send,
     {
-        self.extract_with_metadata(T::metadata()).await
+        T::extract(self).await
     }
 
     /// Extract request as type `T` from request's different parts.
@@ -685,6 +786,95 @@ impl Request {
         Err(ParseError::InvalidContentType)
     }
 
+    cfg_feature! {
+        #![feature = "msgpack"]
+        /// Parse MessagePack body as type `T` from request with default max size limit.
+        #[inline]
+        pub async fn parse_msgpack<'de, T>(&'de mut self) -> Result<T, ParseError>
+        where
+            T: Deserialize<'de>,
+        {
+            self.parse_msgpack_with_max_size(secure_max_size()).await
+        }
+        /// Parse MessagePack body as type `T` from request with max size limit.
+        #[inline]
+        pub async fn parse_msgpack_with_max_size<'de, T>(&'de mut self, max_size: usize) -> Result<T, ParseError>
+        where
+            T: Deserialize<'de>,
+        {
+            if let Some(ctype) = self.content_type() {
+                if is_msgpack(&ctype) {
+                    return self
+                        .payload_with_max_size(max_size)
+                        .await
+                        .and_then(|payload| rmp_serde::from_slice::<T>(payload).map_err(ParseError::SerdeMsgPack));
+                }
+            }
+            Err(ParseError::InvalidContentType)
+        }
+    }
+
+    cfg_feature! {
+        #![feature = "cbor"]
+        /// Parse CBOR body as type `T` from request with default max size limit.
+        #[inline]
+        pub async fn parse_cbor<'de, T>(&'de mut self) -> Result<T, ParseError>
+        where
+            T: Deserialize<'de>,
+        {
+            self.parse_cbor_with_max_size(secure_max_size()).await
+        }
+        /// Parse CBOR body as type `T` from request with max size limit.
+        #[inline]
+        pub async fn parse_cbor_with_max_size<'de, T>(&'de mut self, max_size: usize) -> Result<T, ParseError>
+        where
+            T: Deserialize<'de>,
+        {
+            if let Some(ctype) = self.content_type() {
+                if is_cbor(&ctype) {
+                    return self
+                        .payload_with_max_size(max_size)
+                        .await
+                        .and_then(|payload| {
+                            // Decoded into value first, because ciborium can not deserialize borrowed types.
+                            let value = ciborium::de::from_reader::<ciborium::value::Value, _>(&payload[..])?;
+                            value
+                                .deserialized::<T>()
+                                .map_err(|e| ciborium::de::Error::Semantic(None, e.to_string()).into())
+                        });
+                }
+            }
+            Err(ParseError::InvalidContentType)
+        }
+    }
+
+    cfg_feature! {
+        #![feature = "xml"]
+        /// Parse xml body as type `T` from request with default max size limit.
+        #[inline]
+        pub async fn parse_xml<'de, T>(&'de mut self) -> Result<T, ParseError>
+        where
+            T: Deserialize<'de>,
+        {
+            self.parse_xml_with_max_size(secure_max_size()).await
+        }
+        /// Parse xml body as type `T` from request with max size limit.
+        #[inline]
+        pub async fn parse_xml_with_max_size<'de, T>(&'de mut self, max_size: usize) -> Result<T, ParseError>
+        where
+            T: Deserialize<'de>,
+        {
+            if let Some(ctype) = self.content_type() {
+                if is_xml(&ctype) {
+                    return self.payload_with_max_size(max_size).await.and_then(|payload| {
+                        quick_xml::de::from_str::<T>(std::str::from_utf8(payload)?).map_err(ParseError::SerdeXml)
+                    });
+                }
+            }
+            Err(ParseError::InvalidContentType)
+        }
+    }
+
     /// Parse form body as type `T` from request.
     #[inline]
     pub async fn parse_form<'de, T>(&'de mut self) -> Result<T, ParseError>
@@ -700,6 +890,8 @@ impl Request {
     }
 
     /// Parse json body or form body as type `T` from request with default max size.
+    ///
+    /// MessagePack, CBOR and xml bodies are also parsed if the corresponding features are enabled.
     #[inline]
     pub async fn parse_body<'de, T>(&'de mut self) -> Result<T, ParseError>
     where
@@ -723,11 +915,42 @@ impl Request {
                     .await
                     .and_then(|body| serde_json::from_slice::<T>(body).map_err(ParseError::SerdeJson));
             }
+            #[cfg(feature = "msgpack")]
+            if is_msgpack(&ctype) {
+                return self.parse_msgpack_with_max_size(max_size).await;
+            }
+            #[cfg(feature = "cbor")]
+            if is_cbor(&ctype) {
+                return self.parse_cbor_with_max_size(max_size).await;
+            }
+            #[cfg(feature = "xml")]
+            if is_xml(&ctype) {
+                return self.parse_xml_with_max_size(max_size).await;
+            }
         }
         Err(ParseError::InvalidContentType)
     }
 }
 
+/// Returns true if the content type is MessagePack, such as `application/msgpack`.
+#[cfg(feature = "msgpack")]
+#[inline]
+pub(crate) fn is_msgpack(ctype: &Mime) -> bool {
+    ctype.subtype() == "msgpack" || ctype.subtype() == "x-msgpack" || ctype.subtype() == "vnd.msgpack"
+}
+/// Returns true if the content type is CBOR, such as `application/cbor` or `application/xxx+cbor`.
+#[cfg(feature = "cbor")]
+#[inline]
+pub(crate) fn is_cbor(ctype: &Mime) -> bool {
+    ctype.subtype() == "cbor" || ctype.suffix().map(|s| s == "cbor").unwrap_or(false)
+}
+/// Returns true if the content type is xml, such as `application/xml`, `text/xml` or `application/xxx+xml`.
+#[cfg(feature = "xml")]
+#[inline]
+pub(crate) fn is_xml(ctype: &Mime) -> bool {
+    ctype.subtype() == mime::XML || ctype.suffix() == Some(mime::XML)
+}
+
 #[cfg(test)]
 mod tests {
     use serde::{Deserialize, Serialize};
@@ -824,4 +1047,134 @@ file content\r\n\
         let files = req.files("file1").await.unwrap();
         assert_eq!(files[0].name().unwrap(), "err.txt");
     }
+
+    #[tokio::test]
+    async fn test_payload_with_trailers() {
+        use crate::http::body::Frame;
+        use crate::BoxedError;
+
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-checksum", "5d41402a".parse().unwrap());
+        let frames: Vec<Result<_, BoxedError>> = vec![
+            Ok(Frame::data(Bytes::from("hel"))),
+            Ok(Frame::data(Bytes::from("lo"))),
+            Ok(Frame::trailers(trailers)),
+        ];
+        let body = http_body_util::StreamBody::new(futures_util::stream::iter(frames));
+        let mut req = TestClient::post("http://127.0.0.1:5800/upload")
+            .body(ReqBody::Inner(Box::pin(body)))
+            .build();
+        assert!(req.trailers().is_none());
+        assert_eq!(req.payload().await.unwrap(), "hello");
+        assert_eq!(req.trailers().unwrap().get("x-checksum").unwrap(), "5d41402a");
+    }
+
+    #[tokio::test]
+    async fn test_multipart_stream() {
+        fn build() -> Request {
+            TestClient::post("http://127.0.0.1:5800/upload")
+                .add_header("content-type", "multipart/form-data; boundary=boundary", true)
+                .body(
+                    "--boundary\r\n\
+Content-Disposition: form-data; name=\"money\"\r\n\r\nsh*t\r\n\
+--boundary\r\n\
+Content-Disposition: form-data; name=\"file1\"; filename=\"err.txt\"\r\n\
+Content-Type: text/plain\r\n\r\n\
+file content\r\n\
+--boundary--\r\n",
+                )
+                .build()
+        }
+
+        let mut req = build();
+        let mut parts = req.multipart_stream(MultipartLimits::new()).unwrap();
+        let part = parts.next_part().await.unwrap().unwrap();
+        assert_eq!(part.name(), Some("money"));
+        assert_eq!(part.file_name(), None);
+        assert_eq!(part.text().await.unwrap(), "sh*t");
+        let mut part = parts.next_part().await.unwrap().unwrap();
+        assert_eq!(part.file_name(), Some("err.txt"));
+        assert_eq!(part.content_type(), Some(&mime::TEXT_PLAIN));
+        let mut content = Vec::new();
+        while let Some(chunk) = part.chunk().await.unwrap() {
+            content.extend_from_slice(&chunk);
+        }
+        assert_eq!(content, b"file content");
+        drop(part);
+        assert!(parts.next_part().await.unwrap().is_none());
+
+        async fn read_all(req: &mut Request, limits: MultipartLimits) -> Result<usize, ParseError> {
+            let mut parts = req.multipart_stream(limits)?;
+            let mut size = 0;
+            while let Some(part) = parts.next_part().await? {
+                size += part.bytes().await?.len();
+            }
+            Ok(size)
+        }
+        assert_eq!(
+            read_all(&mut build(), MultipartLimits::new().per_part(12))
+                .await
+                .unwrap(),
+            16
+        );
+        assert!(matches!(
+            read_all(&mut build(), MultipartLimits::new().per_part(8)).await,
+            Err(ParseError::Multer(_))
+        ));
+        assert!(matches!(
+            read_all(&mut build(), MultipartLimits::new().total(64)).await,
+            Err(ParseError::Multer(_))
+        ));
+
+        let mut req = TestClient::get("http://127.0.0.1:5800/").json(&"abc").build();
+        assert!(matches!(
+            req.multipart_stream(MultipartLimits::new()),
+            Err(ParseError::NotMultipart)
+        ));
+    }
+
+    #[cfg(all(feature = "msgpack", feature = "cbor", feature = "xml"))]
+    #[tokio::test]
+    async fn test_parse_binary_and_xml_body() {
+        #[derive(Serialize, Deserialize, Eq, PartialEq, Debug)]
+        struct User {
+            name: String,
+            age: u8,
+        }
+        let user = User {
+            name: "jobs".into(),
+            age: 56,
+        };
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/")
+            .add_header("content-type", "application/msgpack", true)
+            .body(rmp_serde::to_vec_named(&user).unwrap())
+            .build();
+        assert_eq!(req.parse_msgpack::<User>().await.unwrap(), user);
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/")
+            .add_header("content-type", "application/cbor", true)
+            .body({
+                let mut body = vec![];
+                ciborium::ser::into_writer(&user, &mut body).unwrap();
+                body
+            })
+            .build();
+        assert_eq!(req.parse_body::<User>().await.unwrap(), user);
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/")
+            .add_header("content-type", "text/xml; charset=utf-8", true)
+            .body("<user><name>jobs</name><age>56</age></user>")
+            .build();
+        assert_eq!(req.parse_body::<User>().await.unwrap(), user);
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/")
+            .add_header("content-type", "application/xml", true)
+            .body("<user><name>jobs</name><age>56</age></user>")
+            .build();
+        assert!(matches!(
+            req.parse_msgpack::<User>().await,
+            Err(ParseError::InvalidContentType)
+        ));
+    }
 }
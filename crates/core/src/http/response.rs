@@ -9,6 +9,7 @@ use futures_util::stream::{Stream, TryStreamExt};
 use http::header::{HeaderMap, HeaderValue, IntoHeaderName};
 pub use http::response::Parts;
 use http::version::Version;
+use hyper::body::Frame;
 use mime::Mime;
 
 use crate::http::StatusCode;
@@ -30,6 +31,8 @@ pub struct Response {
     pub cookies: CookieJar,
     /// The HTTP body.
     pub body: ResBody,
+    /// The HTTP trailers, they are sent after the body.
+    pub trailers: HeaderMap,
 }
 impl Default for Response {
     #[inline]
@@ -69,6 +72,7 @@ impl From<hyper::Response<ResBody>> for Response {
         Response {
             status_code: Some(status),
             body,
+            trailers: HeaderMap::new(),
             version,
             headers,
             #[cfg(feature = "cookie")]
@@ -84,6 +88,7 @@ impl Response {
         Response {
             status_code: None,
             body: ResBody::None,
+            trailers: HeaderMap::new(),
             version: Version::default(),
             headers: HeaderMap::new(),
             #[cfg(feature = "cookie")]
@@ -98,6 +103,7 @@ impl Response {
         Response {
             status_code: None,
             body: ResBody::None,
+            trailers: HeaderMap::new(),
             version: Version::default(),
             headers: HeaderMap::new(),
             cookies,
@@ -120,6 +126,36 @@ impl Response {
         self.headers = headers
     }
 
+    /// Get trailers reference.
+    #[inline]
+    pub fn trailers(&self) -> &HeaderMap {
+        &self.trailers
+    }
+    /// Get mutable trailers reference.
+    ///
+    /// Trailers are sent after the body over HTTP/2 and HTTP/3. Chunked trailers of HTTP/1.1 are not supported,
+    /// they are discarded with a warning when the request is served over HTTP/1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// #[handler]
+    /// async fn hello(res: &mut Response) {
+    ///     res.render("hello");
+    ///     res.trailers_mut().insert("grpc-status", "0".parse().unwrap());
+    /// }
+    /// ```
+    #[inline]
+    pub fn trailers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.trailers
+    }
+    /// Sets trailers.
+    #[inline]
+    pub fn set_trailers(&mut self, trailers: HeaderMap) {
+        self.trailers = trailers
+    }
+
     /// Modify a header for this response.
     ///
     /// When `overwrite` is set to `true`, If the header is already present, the value will be replaced.
@@ -198,6 +234,7 @@ impl Response {
             #[cfg(not(feature = "cookie"))]
             headers,
             body,
+            trailers,
             ..
         } = self;
 
@@ -208,6 +245,11 @@ impl Response {
             }
         }
 
+        let body = if trailers.is_empty() {
+            body
+        } else {
+            body.with_trailers(trailers)
+        };
         let mut res = hyper::Response::new(body);
         *res.headers_mut() = headers;
         // Default to a 404 if no response code was set
@@ -382,6 +424,12 @@ impl Response {
                     "current body's kind is `ResBody::Stream`, it is not allowed to write bytes",
                 ));
             }
+            ResBody::Frames(_) => {
+                tracing::error!("current body's kind is `ResBody::Frames`, it is not allowed to write bytes");
+                return Err(Error::other(
+                    "current body's kind is `ResBody::Frames`, it is not allowed to write bytes",
+                ));
+            }
             ResBody::Error(_) => {
                 self.body = ResBody::Once(data.into());
             }
@@ -406,12 +454,57 @@ impl Response {
             ResBody::Stream(_) => {
                 return Err(Error::other("current body kind is `ResBody::Stream` already"));
             }
+            ResBody::Frames(_) => {
+                return Err(Error::other("current body kind is `ResBody::Frames` already"));
+            }
             _ => {}
         }
         let mapped = stream.map_ok(Into::into).map_err(Into::into);
         self.body = ResBody::Stream(Box::pin(mapped));
         Ok(())
     }
+    /// Write streaming frames, trailers can be sent by the last frame, over HTTP/2 and HTTP/3 only.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// use futures_util::stream;
+    /// use salvo_core::http::body::Frame;
+    /// use salvo_core::http::header::HeaderMap;
+    ///
+    /// #[handler]
+    /// async fn download(res: &mut Response) {
+    ///     let mut trailers = HeaderMap::new();
+    ///     trailers.insert("x-checksum", "5d41402a".parse().unwrap());
+    ///     let frames = vec![Ok::<_, std::io::Error>(Frame::data("hello".into())), Ok(Frame::trailers(trailers))];
+    ///     res.frame_streaming(stream::iter(frames)).unwrap();
+    /// }
+    /// ```
+    #[inline]
+    pub fn frame_streaming<S, E>(&mut self, stream: S) -> crate::Result<()>
+    where
+        S: Stream<Item = Result<Frame<Bytes>, E>> + Send + 'static,
+        E: Into<Box<dyn StdError + Send + Sync>> + 'static,
+    {
+        match &self.body {
+            ResBody::Once(_) => {
+                return Err(Error::other("current body kind is `ResBody::Once` already"));
+            }
+            ResBody::Chunks(_) => {
+                return Err(Error::other("current body kind is `ResBody::Chunks` already"));
+            }
+            ResBody::Stream(_) => {
+                return Err(Error::other("current body kind is `ResBody::Stream` already"));
+            }
+            ResBody::Frames(_) => {
+                return Err(Error::other("current body kind is `ResBody::Frames` already"));
+            }
+            _ => {}
+        }
+        self.body = ResBody::Frames(Box::pin(stream.map_err(Into::into)));
+        Ok(())
+    }
 }
 
 impl fmt::Debug for Response {
@@ -461,6 +554,44 @@ mod test {
         assert_eq!("hello", &result)
     }
 
+    #[tokio::test]
+    async fn test_body_with_trailers() {
+        use http_body_util::BodyExt;
+
+        let mut res = Response::new();
+        res.render("hello");
+        res.trailers_mut().insert("grpc-status", "0".parse().unwrap());
+        let collected = BodyExt::collect(res.into_hyper().into_body()).await.unwrap();
+        assert_eq!(collected.trailers().unwrap().get("grpc-status").unwrap(), "0");
+        assert_eq!(collected.to_bytes(), "hello");
+
+        let mut body_trailers = HeaderMap::new();
+        body_trailers.insert("x-checksum", "5d41402a".parse().unwrap());
+        let mut res = Response::new();
+        res.frame_streaming(iter(vec![
+            Ok::<_, Box<dyn Error + Send + Sync>>(Frame::data(Bytes::from("hello"))),
+            Ok(Frame::trailers(body_trailers)),
+        ]))
+        .unwrap();
+        assert!(res.write_body("world").is_err());
+        res.trailers_mut().insert("grpc-status", "0".parse().unwrap());
+        let collected = BodyExt::collect(res.into_hyper().into_body()).await.unwrap();
+        let trailers = collected.trailers().unwrap();
+        assert_eq!(trailers.get("x-checksum").unwrap(), "5d41402a");
+        assert_eq!(trailers.get("grpc-status").unwrap(), "0");
+        assert_eq!(collected.to_bytes(), "hello");
+
+        let mut body = ResBody::Frames(Box::pin(iter(vec![
+            Ok(Frame::data(Bytes::from("hello"))),
+            Ok(Frame::trailers(HeaderMap::new())),
+        ])));
+        let mut result = bytes::BytesMut::new();
+        while let Some(Ok(data)) = body.next().await {
+            result.extend_from_slice(&data)
+        }
+        assert_eq!("hello", &result)
+    }
+
     #[tokio::test]
     async fn test_body_stream2() {
         let mut body = ResBody::Stream(Box::pin(iter(vec![
@@ -46,8 +46,8 @@ pub use self::extract::Extractible;
 pub use self::handler::Handler;
 pub use self::http::{Request, Response};
 pub use self::routing::{FlowCtrl, Router};
-pub use self::server::Server;
-pub use self::service::Service;
+pub use self::server::{ProtocolStats, Server, ServerHandle, ServerStats};
+pub use self::service::{Service, ServiceSwapper};
 pub use self::writer::{Piece, Writer};
 /// Result type which has `salvo::Error` as it's error type.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -83,17 +83,30 @@ pub mod prelude {
         #![unix]
         pub use crate::conn::UnixListener;
     }
-    pub use crate::conn::{JoinedListener, Listener, TcpListener};
+    pub use crate::conn::{JoinedListener, LimitListener, Listener, ProxyProtocolListener, TcpListener};
     pub use crate::handler::{self, Handler};
     pub use crate::routing::{FlowCtrl, Router};
     pub use crate::server::Server;
     pub use crate::service::Service;
-    pub use crate::writer::{Json, Piece, Redirect, Text, Writer};
+    pub use crate::writer::{Json, Negotiated, Piece, Redirect, Text, Writer};
+    cfg_feature! {
+        #![feature = "msgpack"]
+        pub use crate::writer::MsgPack;
+    }
+    cfg_feature! {
+        #![feature = "cbor"]
+        pub use crate::writer::Cbor;
+    }
+    cfg_feature! {
+        #![feature = "xml"]
+        pub use crate::writer::Xml;
+    }
 }
 
 #[doc(hidden)]
 pub mod __private {
     pub use once_cell;
+    pub use regex;
     pub use tracing;
 }
 
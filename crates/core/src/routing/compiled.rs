@@ -0,0 +1,331 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use super::{DetectMatched, PathState, Router};
+use crate::http::{Method, Request};
+
+/// `CompiledRouter` is a [`Router`] with a prebuilt index for fast matching.
+///
+/// [`Router::detect`] tries every child router one by one, so the cost grows with the number of routes.
+/// `CompiledRouter` indexes the leading const segments of each child's path filter in a prefix tree,
+/// only children whose const segments are matched by the request path are detected. Children which start
+/// with other kinds of wisps (named, regex, chars...) or other filters are always detected,
+/// so the match priority is the same as [`Router::detect`].
+///
+/// [`Service`](crate::Service) builds it once when it is created.
+pub struct CompiledRouter {
+    router: Arc<Router>,
+    index: RouterIndex,
+    names: HashMap<String, Vec<usize>>,
+}
+
+impl CompiledRouter {
+    /// Create a new `CompiledRouter`.
+    #[inline]
+    pub fn new(router: impl Into<Arc<Router>>) -> Self {
+        let router = router.into();
+        let index = RouterIndex::new(&router);
+        let mut names = HashMap::new();
+        collect_names(&router, &mut vec![], &mut names);
+        CompiledRouter { router, index, names }
+    }
+
+    /// Get the router of this `CompiledRouter`.
+    #[inline]
+    pub fn router(&self) -> &Arc<Router> {
+        &self.router
+    }
+
+    /// Detect the router is matched for current request.
+    #[inline]
+    pub fn detect(&self, req: &mut Request, path_state: &mut PathState) -> Option<DetectMatched> {
+        self.router.detect_indexed(Some(&self.index), req, path_state)
+    }
+
+    /// Detect which methods are allowed for the request's path.
+    #[inline]
+    pub fn allowed_methods(&self, req: &mut Request) -> Vec<Method> {
+        self.router.allowed_methods_indexed(Some(&self.index), req)
+    }
+
+    /// Check is there any router has name.
+    #[inline]
+    pub fn has_names(&self) -> bool {
+        !self.names.is_empty()
+    }
+
+    /// Generate url for the router with the given name.
+    ///
+    /// The url is built from path filters of the named router and all it's ancestors, `params` are
+    /// filled into the named wisps and validated by each wisp's constraint, `queries` are appended as query string.
+    pub fn url_for(
+        &self,
+        name: &str,
+        params: &[(&str, &str)],
+        queries: &[(&str, &str)],
+    ) -> Result<String, UrlForError> {
+        let indexes = self
+            .names
+            .get(name)
+            .ok_or_else(|| UrlForError::UnknownRoute(name.to_owned()))?;
+        let mut segments = Vec::new();
+        let mut router = &*self.router;
+        for index in std::iter::once(None).chain(indexes.iter().map(Some)) {
+            if let Some(index) = index {
+                router = &router.routers[*index];
+            }
+            for filter in &router.filters {
+                if let Some(path_filter) = filter.as_path_filter() {
+                    segments.extend(path_filter.build_url(params)?);
+                }
+            }
+        }
+        let mut url = format!("/{}", segments.join("/"));
+        if !queries.is_empty() {
+            url.push('?');
+            url.push_str(
+                &form_urlencoded::Serializer::new(String::new())
+                    .extend_pairs(queries)
+                    .finish(),
+            );
+        }
+        Ok(url)
+    }
+}
+
+/// Error happened when generate url for a named router.
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum UrlForError {
+    /// There is no router with the name.
+    #[error("router with name `{0}` is not found")]
+    UnknownRoute(String),
+
+    /// The param required by the router's path is missing.
+    #[error("param `{0}` is missing")]
+    MissingParam(String),
+
+    /// The param is not valid for the constraint of the router's path.
+    #[error("param `{name}` is not valid: {reason}")]
+    InvalidParam {
+        /// The name of the param.
+        name: String,
+        /// Why the param is not valid.
+        reason: String,
+    },
+}
+
+fn collect_names(router: &Router, indexes: &mut Vec<usize>, names: &mut HashMap<String, Vec<usize>>) {
+    if let Some(name) = &router.name {
+        if names.contains_key(name) {
+            tracing::warn!(name = %name, "router name is duplicated, only the first one is used");
+        } else {
+            names.insert(name.clone(), indexes.clone());
+        }
+    }
+    for (i, child) in router.routers.iter().enumerate() {
+        indexes.push(i);
+        collect_names(child, indexes, names);
+        indexes.pop();
+    }
+}
+
+impl From<Arc<Router>> for CompiledRouter {
+    #[inline]
+    fn from(router: Arc<Router>) -> Self {
+        CompiledRouter::new(router)
+    }
+}
+
+/// Index of a router's children, it has the same shape with the router tree.
+pub(crate) struct RouterIndex {
+    segments: SegmentNode,
+    pub(crate) children: Vec<RouterIndex>,
+}
+
+/// Prefix tree node of url segments, `routers` are the indexes of children whose
+/// leading const segments end at this node.
+#[derive(Default)]
+struct SegmentNode {
+    routers: Vec<usize>,
+    nodes: HashMap<String, SegmentNode>,
+}
+
+impl RouterIndex {
+    pub(crate) fn new(router: &Router) -> Self {
+        let mut segments = SegmentNode::default();
+        for (i, child) in router.routers.iter().enumerate() {
+            let mut node = &mut segments;
+            if let Some(path_filter) = child.filters.first().and_then(|f| f.as_path_filter()) {
+                for segment in path_filter.const_prefix() {
+                    node = node.nodes.entry(segment.to_owned()).or_default();
+                }
+            }
+            node.routers.push(i);
+        }
+        RouterIndex {
+            segments,
+            children: router.routers.iter().map(RouterIndex::new).collect(),
+        }
+    }
+
+    /// Get indexes of children which may be matched by current path, in the order of them are added.
+    pub(crate) fn candidates(&self, path_state: &PathState) -> Vec<usize> {
+        if path_state.cursor.1 != 0 {
+            // Const wisps should match from the beginning of a segment, the index can not be used.
+            return (0..self.children.len()).collect();
+        }
+        let mut candidates = self.segments.routers.clone();
+        let mut node = &self.segments;
+        for part in &path_state.parts[path_state.cursor.0.min(path_state.parts.len())..] {
+            if let Some(next) = node.nodes.get(part) {
+                candidates.extend_from_slice(&next.routers);
+                node = next;
+            } else {
+                break;
+            }
+        }
+        candidates.sort_unstable();
+        candidates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CompiledRouter, UrlForError};
+    use crate::routing::{PathState, Router};
+    use crate::test::TestClient;
+    use crate::{handler, Response};
+
+    #[handler]
+    async fn handler1(_res: &mut Response) {}
+    #[handler]
+    async fn handler2(_res: &mut Response) {}
+    #[handler]
+    async fn handler3(_res: &mut Response) {}
+
+    #[test]
+    fn test_compiled_detect_same_as_router() {
+        let router = Router::new()
+            .push(Router::with_path("users/<id:num>").get(handler1))
+            .push(Router::with_path("users/new").get(handler2))
+            .push(
+                Router::with_path("users")
+                    .get(handler1)
+                    .push(Router::with_path("<id>/emails").get(handler3)),
+            )
+            .push(Router::with_path("<name>/about").get(handler2))
+            .push(Router::with_path("articles/<id>/comments").get(handler3))
+            .push(Router::with_path("articles").push(Router::with_path("<**rest>").get(handler2)))
+            .push(Router::with_path("a<id:num>b").get(handler3))
+            .push(Router::with_filter_fn(|_, _| true).path("static/<*path>").get(handler1));
+        let urls = [
+            "/",
+            "/users",
+            "/users/12",
+            "/users/new",
+            "/users/12/emails",
+            "/users/about",
+            "/john/about",
+            "/articles",
+            "/articles/12/comments",
+            "/articles/12/likes",
+            "/a12b",
+            "/static/css/site.css",
+            "/unknown/path",
+        ];
+        let compiled = CompiledRouter::new(router);
+        for url in urls {
+            let mut req = TestClient::get(format!("http://local.host{url}")).build();
+            let mut path_state = PathState::new(req.uri().path());
+            let matched = compiled.router().detect(&mut req, &mut path_state);
+
+            let mut compiled_path_state = PathState::new(req.uri().path());
+            let compiled_matched = compiled.detect(&mut req, &mut compiled_path_state);
+            assert_eq!(
+                matched.map(|dm| dm.handler.type_name()),
+                compiled_matched.map(|dm| dm.handler.type_name()),
+                "{url}"
+            );
+            assert_eq!(path_state, compiled_path_state, "{url}");
+        }
+    }
+
+    #[test]
+    fn test_url_for() {
+        let router = Router::with_path("users")
+            .push(
+                Router::with_path("<id:num>")
+                    .name("user.show")
+                    .get(handler1)
+                    .push(Router::with_path("files/<**path>").name("user.files").get(handler2)),
+            )
+            .push(
+                Router::with_path(r"<name:/[a-z]+/>.json")
+                    .name("user.json")
+                    .get(handler3),
+            )
+            .push(Router::with_path("search/<keyword>").name("user.search").get(handler3));
+        let compiled = CompiledRouter::new(router);
+        assert_eq!(
+            compiled.url_for("user.show", &[("id", "12")], &[]).unwrap(),
+            "/users/12"
+        );
+        assert_eq!(
+            compiled
+                .url_for("user.show", &[("id", "12")], &[("page", "2"), ("q", "a b")])
+                .unwrap(),
+            "/users/12?page=2&q=a+b"
+        );
+        assert!(matches!(
+            compiled.url_for("user.show", &[("id", "abc")], &[]),
+            Err(UrlForError::InvalidParam { name, .. }) if name == "id"
+        ));
+        assert_eq!(
+            compiled.url_for("user.show", &[], &[]),
+            Err(UrlForError::MissingParam("id".to_owned()))
+        );
+        assert_eq!(
+            compiled.url_for("user.unknown", &[("id", "12")], &[]),
+            Err(UrlForError::UnknownRoute("user.unknown".to_owned()))
+        );
+        assert_eq!(
+            compiled
+                .url_for("user.files", &[("id", "12"), ("path", "docs/a b.txt")], &[])
+                .unwrap(),
+            "/users/12/files/docs/a%20b.txt"
+        );
+        assert_eq!(
+            compiled
+                .url_for("user.files", &[("id", "12"), ("**path", "")], &[])
+                .unwrap(),
+            "/users/12/files"
+        );
+        assert_eq!(
+            compiled.url_for("user.json", &[("name", "john")], &[]).unwrap(),
+            "/users/john.json"
+        );
+        assert!(compiled.url_for("user.json", &[("name", "John1")], &[]).is_err());
+        assert_eq!(
+            compiled.url_for("user.search", &[("keyword", "a/b")], &[]).unwrap(),
+            "/users/search/a%2Fb"
+        );
+    }
+
+    #[test]
+    fn test_compiled_detect_priority() {
+        let router = Router::new()
+            .push(Router::with_path("<name>").get(handler1))
+            .push(Router::with_path("users").get(handler2));
+        let compiled = CompiledRouter::new(router);
+        let mut req = TestClient::get("http://local.host/users").build();
+        let mut path_state = PathState::new(req.uri().path());
+        let matched = compiled.detect(&mut req, &mut path_state).unwrap();
+        assert_eq!(
+            matched.handler.type_name(),
+            "salvo_core::routing::compiled::tests::handler1"
+        );
+    }
+}
@@ -28,6 +28,18 @@ pub trait Filter: fmt::Debug + Send + Sync + 'static {
     fn type_name(&self) -> &'static str {
         std::any::type_name::<Self>()
     }
+    #[doc(hidden)]
+    fn as_path_filter(&self) -> Option<&PathFilter> {
+        None
+    }
+    /// Get the structured information of this filter, it is used to introspect routers.
+    ///
+    /// The default implementation returns an empty [`FilterInfo`], which means this filter
+    /// does not limit path, methods, host, scheme or port.
+    #[inline]
+    fn info(&self) -> FilterInfo {
+        FilterInfo::default()
+    }
     /// Create a new filter use `And` filter.
     #[inline]
     fn and<F>(self, other: F) -> And<Self, F>
@@ -84,6 +96,129 @@ pub trait Filter: fmt::Debug + Send + Sync + 'static {
     fn filter(&self, req: &mut Request, path: &mut PathState) -> bool;
 }
 
+/// Structured information of a [`Filter`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FilterInfo {
+    /// Path template, like `users/<id>`.
+    pub path: Option<String>,
+    /// Allowed methods, `None` means all methods are allowed and an empty list means no method is allowed.
+    pub methods: Option<Vec<Method>>,
+    /// Allowed host.
+    pub host: Option<String>,
+    /// Allowed uri scheme.
+    pub scheme: Option<Scheme>,
+    /// Allowed port.
+    pub port: Option<u16>,
+}
+impl FilterInfo {
+    /// Create new `FilterInfo` with path template.
+    #[inline]
+    pub fn with_path(path: impl Into<String>) -> Self {
+        FilterInfo {
+            path: Some(path.into()),
+            ..Default::default()
+        }
+    }
+    /// Create new `FilterInfo` with methods.
+    #[inline]
+    pub fn with_methods(methods: Vec<Method>) -> Self {
+        FilterInfo {
+            methods: Some(methods),
+            ..Default::default()
+        }
+    }
+    /// Create new `FilterInfo` with host.
+    #[inline]
+    pub fn with_host(host: impl Into<String>) -> Self {
+        FilterInfo {
+            host: Some(host.into()),
+            ..Default::default()
+        }
+    }
+    /// Create new `FilterInfo` with scheme.
+    #[inline]
+    pub fn with_scheme(scheme: Scheme) -> Self {
+        FilterInfo {
+            scheme: Some(scheme),
+            ..Default::default()
+        }
+    }
+    /// Create new `FilterInfo` with port.
+    #[inline]
+    pub fn with_port(port: u16) -> Self {
+        FilterInfo {
+            port: Some(port),
+            ..Default::default()
+        }
+    }
+
+    /// Merge information of two filters which must be both passed.
+    ///
+    /// Paths are joined, methods are intersected, and other values of `self` are preferred.
+    pub fn and(self, other: FilterInfo) -> FilterInfo {
+        let path = match (self.path, other.path) {
+            (Some(first), Some(second)) => Some(join_path(&first, &second)),
+            (first, second) => first.or(second),
+        };
+        let methods = match (self.methods, other.methods) {
+            (Some(first), Some(second)) => Some(first.into_iter().filter(|m| second.contains(m)).collect()),
+            (first, second) => first.or(second),
+        };
+        FilterInfo {
+            path,
+            methods,
+            host: self.host.or(other.host),
+            scheme: self.scheme.or(other.scheme),
+            port: self.port.or(other.port),
+        }
+    }
+
+    /// Merge information of two filters which one of them should be passed.
+    ///
+    /// Methods are united, and other values are kept only when they are the same in both.
+    pub fn or(self, other: FilterInfo) -> FilterInfo {
+        let methods = match (self.methods, other.methods) {
+            (Some(mut methods), Some(second)) => {
+                for method in second {
+                    if !methods.contains(&method) {
+                        methods.push(method);
+                    }
+                }
+                Some(methods)
+            }
+            _ => None,
+        };
+        fn same<T: PartialEq>(first: Option<T>, second: Option<T>) -> Option<T> {
+            if first == second {
+                first
+            } else {
+                None
+            }
+        }
+        FilterInfo {
+            path: same(self.path, other.path),
+            methods,
+            host: same(self.host, other.host),
+            scheme: same(self.scheme, other.scheme),
+            port: same(self.port, other.port),
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn join_path(first: &str, second: &str) -> String {
+    let first = first.trim_matches('/');
+    let second = second.trim_matches('/');
+    if first.is_empty() {
+        second.to_owned()
+    } else if second.is_empty() {
+        first.to_owned()
+    } else {
+        format!("{first}/{second}")
+    }
+}
+
 /// `FnFilter` accepts a function as it's param, use this function to filter request.
 #[derive(Copy, Clone)]
 #[allow(missing_debug_implementations)]
@@ -181,6 +316,27 @@ mod tests {
         assert!(delete() == MethodFilter(Method::DELETE));
     }
 
+    #[test]
+    fn test_filter_info() {
+        let info = path("users/<id>").and(get().or(post())).and(host("localhost")).info();
+        assert_eq!(info.path.as_deref(), Some("users/<id>"));
+        assert_eq!(info.methods, Some(vec![Method::GET, Method::POST]));
+        assert_eq!(info.host.as_deref(), Some("localhost"));
+
+        let info = get().or(FnFilter(|_: &mut Request, _: &mut PathState| true)).info();
+        assert_eq!(info.methods, None);
+
+        let info = path("users").info().and(path("/<id>/").info()).and(get().info());
+        assert_eq!(info.path.as_deref(), Some("users/<id>"));
+        assert_eq!(info.methods, Some(vec![Method::GET]));
+
+        // No method can pass both filters.
+        let info = get().and(post()).info();
+        assert_eq!(info.methods, Some(vec![]));
+        let info = get().and(post()).or(put()).info();
+        assert_eq!(info.methods, Some(vec![Method::PUT]));
+    }
+
     #[test]
     fn test_opts() {
         fn has_one(_req: &mut Request, path: &mut PathState) -> bool {
@@ -1,7 +1,7 @@
 use std::fmt::{self, Formatter};
 
 use crate::http::Request;
-use crate::routing::{Filter, PathState};
+use crate::routing::{Filter, FilterInfo, PathState};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Or<T, U> {
@@ -14,6 +14,10 @@ where
     T: Filter + Send,
     U: Filter + Send,
 {
+    #[inline]
+    fn info(&self) -> FilterInfo {
+        self.first.info().or(self.second.info())
+    }
     #[inline]
     fn filter(&self, req: &mut Request, state: &mut PathState) -> bool {
         if self.first.filter(req, state) {
@@ -62,6 +66,10 @@ where
     T: Filter,
     U: Filter,
 {
+    #[inline]
+    fn info(&self) -> FilterInfo {
+        self.first.info().and(self.second.info())
+    }
     #[inline]
     fn filter(&self, req: &mut Request, state: &mut PathState) -> bool {
         if !self.first.filter(req, state) {
@@ -83,6 +91,10 @@ where
     T: Filter,
     F: Fn(&mut Request, &mut PathState) -> bool + Send + Sync + 'static,
 {
+    #[inline]
+    fn info(&self) -> FilterInfo {
+        self.filter.info()
+    }
     #[inline]
     fn filter(&self, req: &mut Request, state: &mut PathState) -> bool {
         if !self.filter.filter(req, state) {
@@ -2,7 +2,7 @@ use std::fmt::{self, Formatter};
 
 use crate::http::uri::Scheme;
 use crate::http::{header, Method, Request};
-use crate::routing::{Filter, PathState};
+use crate::routing::{Filter, FilterInfo, PathState};
 
 /// Filter by request method
 #[derive(Clone, PartialEq, Eq)]
@@ -14,6 +14,10 @@ impl MethodFilter {
     }
 }
 impl Filter for MethodFilter {
+    #[inline]
+    fn info(&self) -> FilterInfo {
+        FilterInfo::with_methods(vec![self.0.clone()])
+    }
     #[inline]
     fn filter(&self, req: &mut Request, _state: &mut PathState) -> bool {
         req.method() == self.0
@@ -46,6 +50,10 @@ impl SchemeFilter {
     }
 }
 impl Filter for SchemeFilter {
+    #[inline]
+    fn info(&self) -> FilterInfo {
+        FilterInfo::with_scheme(self.scheme.clone())
+    }
     #[inline]
     fn filter(&self, req: &mut Request, _state: &mut PathState) -> bool {
         req.uri().scheme().map(|s| s == &self.scheme).unwrap_or(self.lack)
@@ -81,6 +89,10 @@ impl HostFilter {
     }
 }
 impl Filter for HostFilter {
+    #[inline]
+    fn info(&self) -> FilterInfo {
+        FilterInfo::with_host(self.host.clone())
+    }
     #[inline]
     fn filter(&self, req: &mut Request, _state: &mut PathState) -> bool {
         // Http1, if `fix-http1-request-uri` feature is disabled, host is lack. so use header host instead.
@@ -127,6 +139,10 @@ impl PortFilter {
     }
 }
 impl Filter for PortFilter {
+    #[inline]
+    fn info(&self) -> FilterInfo {
+        FilterInfo::with_port(self.port)
+    }
     #[inline]
     fn filter(&self, req: &mut Request, _state: &mut PathState) -> bool {
         // Http1, if `fix-http1-request-uri` feature is disabled, port is lack. so use header host instead.
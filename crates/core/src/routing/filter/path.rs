@@ -1,14 +1,16 @@
 use std::collections::HashMap;
 use std::fmt::{self, Formatter};
+use std::str::FromStr;
 use std::sync::Arc;
 
 use indexmap::IndexSet;
 use once_cell::sync::Lazy;
 use parking_lot::RwLock;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
 use regex::Regex;
 
 use crate::http::Request;
-use crate::routing::{Filter, PathState};
+use crate::routing::{Filter, FilterInfo, PathState, UrlForError};
 
 /// PathWisp
 pub trait PathWisp: Send + Sync + fmt::Debug + 'static {
@@ -35,12 +37,42 @@ pub trait WispBuilder: Send + Sync {
 
 type WispBuilderMap = RwLock<HashMap<String, Arc<Box<dyn WispBuilder>>>>;
 static WISP_BUILDERS: Lazy<WispBuilderMap> = Lazy::new(|| {
-    let mut map: HashMap<String, Arc<Box<dyn WispBuilder>>> = HashMap::with_capacity(8);
+    let mut map: HashMap<String, Arc<Box<dyn WispBuilder>>> = HashMap::with_capacity(16);
     map.insert("num".into(), Arc::new(Box::new(CharsWispBuilder::new(is_num))));
     map.insert("hex".into(), Arc::new(Box::new(CharsWispBuilder::new(is_hex))));
+    map.insert("i8".into(), Arc::new(Box::new(ParsedWispBuilder::new::<i8>())));
+    map.insert("i16".into(), Arc::new(Box::new(ParsedWispBuilder::new::<i16>())));
+    map.insert("i32".into(), Arc::new(Box::new(ParsedWispBuilder::new::<i32>())));
+    map.insert("i64".into(), Arc::new(Box::new(ParsedWispBuilder::new::<i64>())));
+    map.insert("u8".into(), Arc::new(Box::new(ParsedWispBuilder::new::<u8>())));
+    map.insert("u16".into(), Arc::new(Box::new(ParsedWispBuilder::new::<u16>())));
+    map.insert("u32".into(), Arc::new(Box::new(ParsedWispBuilder::new::<u32>())));
+    map.insert("u64".into(), Arc::new(Box::new(ParsedWispBuilder::new::<u64>())));
+    map.insert(
+        "uuid".into(),
+        Arc::new(Box::new(ParsedWispBuilder::with_checker(is_uuid))),
+    );
+    map.insert(
+        "slug".into(),
+        Arc::new(Box::new(ParsedWispBuilder::with_checker(is_slug))),
+    );
     RwLock::new(map)
 });
 
+// https://url.spec.whatwg.org/#path-percent-encode-set
+const SEGMENT_ENCODE_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}')
+    .add(b'%')
+    .add(b'/');
+
 #[inline]
 fn is_num(ch: char) -> bool {
     ch.is_ascii_digit()
@@ -49,6 +81,21 @@ fn is_num(ch: char) -> bool {
 fn is_hex(ch: char) -> bool {
     ch.is_ascii_hexdigit()
 }
+/// Hyphenated uuid, such as `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+fn is_uuid(value: &str) -> bool {
+    value.len() == 36
+        && value.char_indices().all(|(i, ch)| match i {
+            8 | 13 | 18 | 23 => ch == '-',
+            _ => ch.is_ascii_hexdigit(),
+        })
+}
+/// Lowercase alphanumeric words joined by single hyphens, such as `hello-world-2`.
+fn is_slug(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .split('-')
+            .all(|word| !word.is_empty() && word.chars().all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit()))
+}
 
 /// Enum of all wisp kinds.
 pub enum WispKind {
@@ -60,6 +107,8 @@ pub enum WispKind {
     Chars(CharsWisp),
     /// RegexWisp.
     Regex(RegexWisp),
+    /// ParsedWisp.
+    Parsed(ParsedWisp),
     /// CombWisp.
     Comb(CombWisp),
 }
@@ -71,6 +120,7 @@ impl PathWisp for WispKind {
             Self::Named(wisp) => wisp.validate(),
             Self::Chars(wisp) => wisp.validate(),
             Self::Regex(wisp) => wisp.validate(),
+            Self::Parsed(wisp) => wisp.validate(),
             Self::Comb(wisp) => wisp.validate(),
         }
     }
@@ -81,6 +131,7 @@ impl PathWisp for WispKind {
             Self::Named(wisp) => wisp.detect(state),
             Self::Chars(wisp) => wisp.detect(state),
             Self::Regex(wisp) => wisp.detect(state),
+            Self::Parsed(wisp) => wisp.detect(state),
             Self::Comb(wisp) => wisp.detect(state),
         }
     }
@@ -92,6 +143,7 @@ impl fmt::Debug for WispKind {
             Self::Named(wisp) => wisp.fmt(f),
             Self::Chars(wisp) => wisp.fmt(f),
             Self::Regex(wisp) => wisp.fmt(f),
+            Self::Parsed(wisp) => wisp.fmt(f),
             Self::Comb(wisp) => wisp.fmt(f),
         }
     }
@@ -120,6 +172,12 @@ impl From<RegexWisp> for WispKind {
         Self::Regex(wisp)
     }
 }
+impl From<ParsedWisp> for WispKind {
+    #[inline]
+    fn from(wisp: ParsedWisp) -> Self {
+        Self::Parsed(wisp)
+    }
+}
 impl From<CombWisp> for WispKind {
     #[inline]
     fn from(wisp: CombWisp) -> Self {
@@ -127,6 +185,86 @@ impl From<CombWisp> for WispKind {
     }
 }
 
+impl WispKind {
+    /// Build url segment of this wisp with params, returns error if param is missing or not valid.
+    fn build_url(&self, params: &[(&str, &str)]) -> Result<String, UrlForError> {
+        match self {
+            Self::Const(wisp) => Ok(utf8_percent_encode(&wisp.0, SEGMENT_ENCODE_SET).to_string()),
+            Self::Named(wisp) => {
+                let value = find_param(params, &wisp.0)?;
+                if value.is_empty() && !wisp.0.starts_with("**") {
+                    return Err(UrlForError::InvalidParam {
+                        name: wisp.0.clone(),
+                        reason: "should not be empty".to_owned(),
+                    });
+                }
+                Ok(encode_param(&wisp.0, value))
+            }
+            Self::Chars(wisp) => {
+                let value = find_param(params, &wisp.name)?;
+                let width = value.chars().count();
+                if !value.chars().all(|ch| (wisp.checker)(ch))
+                    || width < wisp.min_width
+                    || wisp.max_width.map(|max| width > max).unwrap_or(false)
+                {
+                    return Err(UrlForError::InvalidParam {
+                        name: wisp.name.clone(),
+                        reason: format!("value `{value}` is not matched by chars constraint"),
+                    });
+                }
+                Ok(encode_param(&wisp.name, value))
+            }
+            Self::Regex(wisp) => {
+                let value = find_param(params, &wisp.name)?;
+                let matched = wisp
+                    .regex
+                    .find(value)
+                    .map(|m| m.start() == 0 && m.end() == value.len())
+                    .unwrap_or(false);
+                if !matched {
+                    return Err(UrlForError::InvalidParam {
+                        name: wisp.name.clone(),
+                        reason: format!("value `{value}` is not matched by regex `{}`", wisp.regex.as_str()),
+                    });
+                }
+                Ok(encode_param(&wisp.name, value))
+            }
+            Self::Parsed(wisp) => {
+                let value = find_param(params, &wisp.name)?;
+                if !(wisp.checker)(value) {
+                    return Err(UrlForError::InvalidParam {
+                        name: wisp.name.clone(),
+                        reason: format!("value `{value}` can not be parsed"),
+                    });
+                }
+                Ok(encode_param(&wisp.name, value))
+            }
+            Self::Comb(wisp) => wisp.0.iter().map(|wisp| wisp.build_url(params)).collect(),
+        }
+    }
+}
+#[inline]
+fn find_param<'a>(params: &[(&str, &'a str)], name: &str) -> Result<&'a str, UrlForError> {
+    let trimed_name = name.trim_start_matches('*');
+    params
+        .iter()
+        .find(|(key, _)| *key == name || *key == trimed_name)
+        .map(|(_, value)| *value)
+        .ok_or_else(|| UrlForError::MissingParam(name.to_owned()))
+}
+#[inline]
+fn encode_param(name: &str, value: &str) -> String {
+    if name.starts_with('*') {
+        value
+            .split('/')
+            .map(|part| utf8_percent_encode(part, SEGMENT_ENCODE_SET).to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    } else {
+        utf8_percent_encode(value, SEGMENT_ENCODE_SET).to_string()
+    }
+}
+
 /// RegexWispBuilder
 pub struct RegexWispBuilder(Regex);
 impl RegexWispBuilder {
@@ -284,6 +422,60 @@ impl PathWisp for CharsWisp {
     }
 }
 
+/// ParsedWispBuilder
+///
+/// Build [`ParsedWisp`] which only matches the url segment accepted by the checker, it is used by the built-in
+/// typed wisps like `<id:u64>`, `<id:i32>`, `<id:uuid>` and `<slug:slug>`.
+pub struct ParsedWispBuilder(Arc<dyn Fn(&str) -> bool + Send + Sync + 'static>);
+impl ParsedWispBuilder {
+    /// Create new `ParsedWispBuilder`, the segment is matched only if it can be parsed to `T`.
+    #[inline]
+    pub fn new<T: FromStr>() -> Self {
+        Self::with_checker(|value| value.parse::<T>().is_ok())
+    }
+    /// Create new `ParsedWispBuilder` with a custom checker.
+    #[inline]
+    pub fn with_checker<C>(checker: C) -> Self
+    where
+        C: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        Self(Arc::new(checker))
+    }
+}
+impl WispBuilder for ParsedWispBuilder {
+    fn build(&self, name: String, _sign: String, _args: Vec<String>) -> Result<WispKind, String> {
+        Ok(ParsedWisp {
+            name,
+            checker: self.0.clone(),
+        }
+        .into())
+    }
+}
+
+/// Parsed wisp match the whole part in url segment only if the checker accepts it.
+pub struct ParsedWisp {
+    name: String,
+    checker: Arc<dyn Fn(&str) -> bool + Send + Sync + 'static>,
+}
+impl fmt::Debug for ParsedWisp {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "ParsedWisp {{ name: {:?} }}", self.name)
+    }
+}
+impl PathWisp for ParsedWisp {
+    #[inline]
+    fn detect(&self, state: &mut PathState) -> bool {
+        let picked = match state.pick() {
+            Some(picked) if !picked.is_empty() && (self.checker)(picked) => picked.to_owned(),
+            _ => return false,
+        };
+        state.forward(picked.len());
+        state.params.insert(self.name.clone(), picked);
+        true
+    }
+}
+
 /// Comb wisp is a group of other kind of wisps in the same url segment.
 #[derive(Debug)]
 pub struct CombWisp(pub Vec<WispKind>);
@@ -788,6 +980,7 @@ impl PathParser {
                 WispKind::Named(wisp) => Some(&wisp.0),
                 WispKind::Chars(wisp) => Some(&wisp.name),
                 WispKind::Regex(wisp) => Some(&wisp.name),
+                WispKind::Parsed(wisp) => Some(&wisp.name),
                 WispKind::Comb(comb) => {
                     comb.validate()?;
                     self.validate(&comb.0, all_names)?;
@@ -850,6 +1043,14 @@ impl fmt::Debug for PathFilter {
     }
 }
 impl Filter for PathFilter {
+    #[inline]
+    fn as_path_filter(&self) -> Option<&PathFilter> {
+        Some(self)
+    }
+    #[inline]
+    fn info(&self) -> FilterInfo {
+        FilterInfo::with_path(self.raw_value.trim_matches('/'))
+    }
     #[inline]
     fn filter(&self, _req: &mut Request, state: &mut PathState) -> bool {
         self.detect(state)
@@ -883,12 +1084,53 @@ impl PathFilter {
         let mut builders = WISP_BUILDERS.write();
         builders.insert(name.into(), Arc::new(Box::new(builder)));
     }
+    /// Register new path wisp which only matches the url segment can be parsed to `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use salvo_core::routing::PathFilter;
+    ///
+    /// struct Color(String);
+    /// impl FromStr for Color {
+    ///     type Err = String;
+    ///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+    ///         match s {
+    ///             "red" | "green" | "blue" => Ok(Color(s.to_owned())),
+    ///             _ => Err(format!("unknown color: {s}")),
+    ///         }
+    ///     }
+    /// }
+    /// PathFilter::register_wisp_parser::<Color>("color");
+    /// let filter = PathFilter::new("/paint/<color:color>");
+    /// ```
+    #[inline]
+    pub fn register_wisp_parser<T: FromStr>(name: impl Into<String>) {
+        Self::register_wisp_builder(name, ParsedWispBuilder::new::<T>());
+    }
     /// Register new path part regex.
     #[inline]
     pub fn register_wisp_regex(name: impl Into<String>, regex: Regex) {
         let mut builders = WISP_BUILDERS.write();
         builders.insert(name.into(), Arc::new(Box::new(RegexWispBuilder::new(regex))));
     }
+    /// Get the leading const segments of this path, they must be matched by whole url segments.
+    #[inline]
+    pub(crate) fn const_prefix(&self) -> impl Iterator<Item = &str> {
+        self.path_wisps.iter().map_while(|wisp| match wisp {
+            WispKind::Const(wisp) => Some(&*wisp.0),
+            _ => None,
+        })
+    }
+    /// Build url segments of this path with params, the params are validated by each wisp's constraint.
+    pub(crate) fn build_url(&self, params: &[(&str, &str)]) -> Result<Vec<String>, UrlForError> {
+        self.path_wisps
+            .iter()
+            .map(|wisp| wisp.build_url(params))
+            .filter(|segment| !matches!(segment, Ok(segment) if segment.is_empty()))
+            .collect()
+    }
     /// Detect is that path is match.
     pub fn detect(&self, state: &mut PathState) -> bool {
         let original_cursor = state.cursor;
@@ -1180,4 +1422,51 @@ mod tests {
         let mut state = PathState::new("/users/12/facebook/insights/23");
         assert!(filter.detect(&mut state));
     }
+    #[test]
+    fn test_detect_typed() {
+        let filter = PathFilter::new("/items/<id:u32>");
+        let mut state = PathState::new("/items/42");
+        assert!(filter.detect(&mut state));
+        assert_eq!(state.params["id"], "42");
+        let mut state = PathState::new("/items/99999999999999999999");
+        assert!(!filter.detect(&mut state));
+        let mut state = PathState::new("/items/-1");
+        assert!(!filter.detect(&mut state));
+
+        let filter = PathFilter::new("/items/<id:i32>.json");
+        let mut state = PathState::new("/items/-12.json");
+        assert!(filter.detect(&mut state));
+        assert_eq!(state.params["id"], "-12");
+
+        let filter = PathFilter::new("/users/<id:uuid>");
+        let mut state = PathState::new("/users/67e55044-10b1-426f-9247-bb680e5fe0c8");
+        assert!(filter.detect(&mut state));
+        let mut state = PathState::new("/users/67e55044-10b1-426f-9247-bb680e5fe0cx");
+        assert!(!filter.detect(&mut state));
+
+        let filter = PathFilter::new("/posts/<slug:slug>");
+        let mut state = PathState::new("/posts/hello-world-2");
+        assert!(filter.detect(&mut state));
+        let mut state = PathState::new("/posts/hello--world");
+        assert!(!filter.detect(&mut state));
+    }
+    #[test]
+    fn test_detect_custom_parser() {
+        #[derive(Debug)]
+        struct Color;
+        impl std::str::FromStr for Color {
+            type Err = ();
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                matches!(s, "red" | "green" | "blue").then_some(Color).ok_or(())
+            }
+        }
+        PathFilter::register_wisp_parser::<Color>("color");
+        let filter = PathFilter::new("/paint/<color:color>");
+        let mut state = PathState::new("/paint/green");
+        assert!(filter.detect(&mut state));
+        let mut state = PathState::new("/paint/pink");
+        assert!(!filter.detect(&mut state));
+        assert!(filter.build_url(&[("color", "pink")]).is_err());
+        assert_eq!(filter.build_url(&[("color", "red")]).unwrap(), vec!["paint", "red"]);
+    }
 }
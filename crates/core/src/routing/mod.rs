@@ -1,30 +1,57 @@
 //! Routing and filters
 //! Router can route http requests to different handlers.
 
+mod compiled;
 pub mod filter;
+pub(crate) mod mount;
 mod router;
+pub(crate) mod table;
+pub use compiled::{CompiledRouter, UrlForError};
 pub use filter::*;
-pub use router::{DetectMatched, Router};
+pub use router::{DetectMatched, RouteInfo, Router};
+pub use table::RouteTable;
 
 use std::borrow::Cow;
 use std::sync::Arc;
 
 use indexmap::IndexMap;
 
+use crate::catcher::Catcher;
 use crate::http::{Request, Response};
 use crate::{Depot, Handler};
 
 #[doc(hidden)]
 pub type PathParams = IndexMap<String, String>;
 #[doc(hidden)]
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct PathState {
     pub(crate) parts: Vec<String>,
     /// (row, col), row is the index of parts, col is the index of char in the part.
     pub(crate) cursor: (usize, usize),
     pub(crate) params: PathParams,
-    pub(crate) end_slash: bool, // For rest match, we want include the last slash.
+    pub(crate) end_slash: bool,  // For rest match, we want include the last slash.
+    pub(crate) once_ended: bool, // Some router's filters are all matched when the path is ended.
+    /// Catcher of the deepest router whose filters are matched, with the row of cursor after matched.
+    pub(crate) catcher: Option<(usize, Arc<Catcher>)>,
 }
+impl PartialEq for PathState {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.parts == other.parts
+            && self.cursor == other.cursor
+            && self.params == other.params
+            && self.end_slash == other.end_slash
+            && self.once_ended == other.once_ended
+            && match (&self.catcher, &other.catcher) {
+                (Some((row, catcher)), Some((other_row, other_catcher))) => {
+                    row == other_row && Arc::ptr_eq(catcher, other_catcher)
+                }
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+impl Eq for PathState {}
 impl PathState {
     /// Create new `PathState`.
     #[inline]
@@ -47,6 +74,8 @@ impl PathState {
             cursor: (0, 0),
             params: PathParams::new(),
             end_slash,
+            once_ended: false,
+            catcher: None,
         }
     }
 
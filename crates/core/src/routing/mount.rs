@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::{CompiledRouter, Filter, PathState};
+use crate::http::uri::{PathAndQuery, Uri};
+use crate::http::{Request, Response};
+use crate::writer::negotiated::AllowedMediaTypes;
+use crate::writer::SerializerRegistry;
+use crate::{Depot, FlowCtrl, Handler, Service};
+
+/// The param used to pass the count of unmatched segments from [`MountFilter`] to [`MountedService`].
+const REST_SEGMENTS_PARAM: &str = "**__salvo_mount_rest";
+
+/// Filter which matches all the rest path, the rest segments are handled by the mounted service.
+#[derive(Debug)]
+pub(crate) struct MountFilter;
+impl Filter for MountFilter {
+    #[inline]
+    fn filter(&self, _req: &mut Request, state: &mut PathState) -> bool {
+        // Mount prefix must be matched by whole url segments.
+        if state.cursor.1 != 0 {
+            return false;
+        }
+        let rest = state.parts.len().saturating_sub(state.cursor.0);
+        state.params.insert(REST_SEGMENTS_PARAM.into(), rest.to_string());
+        state.cursor = (state.parts.len(), 0);
+        true
+    }
+}
+
+/// Mount prefix of the request injected into depot by [`MountedService`], it prefixes urls generated by
+/// [`Depot::url_for`].
+pub(crate) struct MountPrefix(pub(crate) String);
+
+/// Handler which strips the mount prefix from request's uri and passes the request to the mounted service.
+pub(crate) struct MountedService(pub(crate) Arc<Service>);
+#[async_trait]
+impl Handler for MountedService {
+    #[inline]
+    fn as_mounted_service(&self) -> Option<&Service> {
+        Some(&self.0)
+    }
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, _ctrl: &mut FlowCtrl) {
+        let rest = req
+            .params
+            .remove(REST_SEGMENTS_PARAM)
+            .and_then(|rest| rest.parse::<usize>().ok())
+            .unwrap_or_default();
+        let original_uri = req.uri().clone();
+        let path = original_uri.path();
+        // Use the raw segments, so percent encoded chars in the rest path are kept as it is.
+        let segments = path.split('/').filter(|s| !s.is_empty()).collect::<Vec<_>>();
+        let (prefix, rest) = segments.split_at(segments.len().saturating_sub(rest));
+        let mut rest_path = format!("/{}", rest.join("/"));
+        if !rest.is_empty() && path.ends_with('/') {
+            rest_path.push('/');
+        }
+        let rest_path = match original_uri.query() {
+            Some(query) => format!("{rest_path}?{query}"),
+            None => rest_path,
+        };
+        let mut parts = original_uri.clone().into_parts();
+        parts.path_and_query = rest_path.parse::<PathAndQuery>().ok();
+        if let Ok(uri) = Uri::from_parts(parts) {
+            *req.uri_mut() = uri;
+        }
+
+        let original_prefix = req.mount_prefix.clone();
+        #[cfg(feature = "cookie")]
+        let original_cookie_key = req.cookie_key.clone();
+        if !prefix.is_empty() {
+            req.mount_prefix = format!("{}/{}", original_prefix, prefix.join("/"));
+        }
+        let handler = self.0.hyper_handler(
+            req.local_addr.clone(),
+            req.remote_addr.clone(),
+            req.scheme.clone(),
+            None,
+        );
+        // The mounted service injects it's own router, serializers and allowed media types, the ones of parent
+        // are restored after it.
+        let original_router = depot.scrape::<Arc<CompiledRouter>>();
+        let original_serializers = depot.obtain::<Arc<SerializerRegistry>>().cloned();
+        let original_media_types = depot.scrape::<AllowedMediaTypes>();
+        let original_mount_prefix = depot.scrape::<MountPrefix>();
+        if !req.mount_prefix.is_empty() {
+            depot.inject(MountPrefix(req.mount_prefix.clone()));
+        }
+        handler.process(req, depot, res).await;
+        depot.scrape::<Arc<CompiledRouter>>();
+        depot.scrape::<Arc<SerializerRegistry>>();
+        depot.scrape::<AllowedMediaTypes>();
+        depot.scrape::<MountPrefix>();
+        if let Some(router) = original_router {
+            depot.inject(router);
+        }
+        if let Some(serializers) = original_serializers {
+            depot.inject(serializers);
+        }
+        if let Some(media_types) = original_media_types {
+            depot.inject(media_types);
+        }
+        if let Some(mount_prefix) = original_mount_prefix {
+            depot.inject(mount_prefix);
+        }
+        req.mount_prefix = original_prefix;
+        #[cfg(feature = "cookie")]
+        {
+            req.cookie_key = original_cookie_key;
+        }
+        *req.uri_mut() = original_uri;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catcher::Catcher;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[tokio::test]
+    async fn test_mount() {
+        #[handler]
+        async fn show_user(req: &mut Request) -> String {
+            format!(
+                "{} {} {} {}",
+                req.mount_prefix(),
+                req.uri().path(),
+                req.param::<String>("tenant").unwrap_or_default(),
+                req.param::<String>("id").unwrap_or_default()
+            )
+        }
+        #[handler]
+        async fn not_found(res: &mut Response, ctrl: &mut FlowCtrl) {
+            if let Some(StatusCode::NOT_FOUND) = res.status_code {
+                res.render("admin not found");
+                ctrl.skip_rest();
+            }
+        }
+        let admin =
+            Service::new(Router::with_path("users/<id>").get(show_user)).catcher(Catcher::default().hoop(not_found));
+        let router = Router::new().push(Router::with_path("tenants/<tenant>").mount("admin", admin));
+        let service = Service::new(router);
+
+        async fn access(service: &Service, url: &str) -> (StatusCode, String) {
+            let mut res = TestClient::get(url).send(service).await;
+            (res.status_code.unwrap(), res.take_string().await.unwrap())
+        }
+        assert_eq!(
+            access(&service, "http://127.0.0.1:5800/tenants/acme/admin/users/12?tab=emails").await,
+            (StatusCode::OK, "/tenants/acme/admin /users/12 acme 12".to_owned())
+        );
+        assert_eq!(
+            access(&service, "http://127.0.0.1:5800/tenants/acme/admin/posts").await,
+            (StatusCode::NOT_FOUND, "admin not found".to_owned())
+        );
+        let (status, body) = access(&service, "http://127.0.0.1:5800/tenants/acme/adminx/users/12").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_ne!(body, "admin not found");
+    }
+
+    #[tokio::test]
+    async fn test_mount_url_for() {
+        #[handler]
+        async fn show_user(depot: &mut Depot) -> String {
+            depot.url_for("user", &[("id", "12")], &[]).unwrap()
+        }
+        #[handler]
+        async fn parent_url(req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+            ctrl.call_next(req, depot, res).await;
+            let child = res.take_string().await.unwrap();
+            let parent = depot.url_for("tenant", &[("tenant", "acme")], &[]).unwrap();
+            res.render(format!("{child} {parent}"));
+        }
+        #[handler]
+        async fn empty() {}
+        let admin = Service::new(Router::with_path("users/<id>").name("user").get(show_user));
+        let router = Router::new().hoop(parent_url).push(
+            Router::with_path("tenants/<tenant>")
+                .name("tenant")
+                .get(empty)
+                .mount("admin", admin),
+        );
+        let service = Service::new(router);
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/tenants/acme/admin/users/12")
+            .send(&service)
+            .await;
+        assert_eq!(
+            res.take_string().await.unwrap(),
+            "/tenants/acme/admin/users/12 /tenants/acme"
+        );
+    }
+}
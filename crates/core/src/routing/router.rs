@@ -1,11 +1,25 @@
 use std::fmt::{self, Formatter};
 use std::sync::Arc;
 
+use super::compiled::RouterIndex;
 use super::filter;
-use super::{Filter, FnFilter, PathFilter, PathState};
+use super::mount::{MountFilter, MountedService};
+use super::{Filter, FilterInfo, FnFilter, PathFilter, PathState};
+use crate::catcher::Catcher;
 use crate::handler::{Handler, WhenHoop};
 use crate::http::uri::Scheme;
-use crate::{Depot, Request};
+use crate::http::Method;
+use crate::{Depot, Request, Service};
+
+const COMMON_METHODS: [Method; 7] = [
+    Method::GET,
+    Method::HEAD,
+    Method::POST,
+    Method::PUT,
+    Method::DELETE,
+    Method::PATCH,
+    Method::OPTIONS,
+];
 
 /// Router struct is used for route request to different handlers.
 ///
@@ -92,11 +106,66 @@ pub struct Router {
     pub hoops: Vec<Arc<dyn Handler>>,
     /// handler is the handler of current router.
     pub handler: Option<Arc<dyn Handler>>,
+    /// name is used to generate url for current router.
+    pub name: Option<String>,
+    /// catcher is used to catch errors produced in current router or it's descendants.
+    pub catcher: Option<Arc<Catcher>>,
 }
+/// Information of a route, it is an item of the flattened route table returned by [`Router::routes`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct RouteInfo {
+    /// Full path template of the route, like `/users/<id>`.
+    pub path: String,
+    /// Allowed methods, empty means all methods are allowed.
+    pub methods: Vec<Method>,
+    /// Allowed host.
+    pub host: Option<String>,
+    /// Allowed uri scheme.
+    pub scheme: Option<Scheme>,
+    /// Allowed port.
+    pub port: Option<u16>,
+    /// Name of the router.
+    pub name: Option<String>,
+    /// Type names of the middlewares, from the root router to the router has handler.
+    pub hoops: Vec<&'static str>,
+    /// Type name of the handler.
+    pub handler: &'static str,
+    /// Type id of the handler.
+    pub handler_type_id: std::any::TypeId,
+}
+
+impl fmt::Display for RouteInfo {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.methods.is_empty() {
+            write!(f, "*")?;
+        } else {
+            let methods = self.methods.iter().map(|m| m.as_str()).collect::<Vec<_>>();
+            write!(f, "{}", methods.join(","))?;
+        }
+        write!(f, " ")?;
+        if let Some(scheme) = &self.scheme {
+            write!(f, "{scheme}://")?;
+        }
+        if let Some(host) = &self.host {
+            write!(f, "{host}")?;
+        }
+        if let Some(port) = self.port {
+            write!(f, ":{port}")?;
+        }
+        write!(f, "{}", self.path)?;
+        if !self.hoops.is_empty() {
+            write!(f, " [{}]", self.hoops.join(" > "))?;
+        }
+        write!(f, " -> {}", self.handler)
+    }
+}
+
 #[doc(hidden)]
 pub struct DetectMatched {
     pub hoops: Vec<Arc<dyn Handler>>,
     pub handler: Arc<dyn Handler>,
+    pub catcher: Option<Arc<Catcher>>,
 }
 
 impl Default for Router {
@@ -115,6 +184,8 @@ impl Router {
             filters: Vec::new(),
             hoops: Vec::new(),
             handler: None,
+            name: None,
+            catcher: None,
         }
     }
 
@@ -153,41 +224,175 @@ impl Router {
 
     /// Detect current router is matched for current request.
     pub fn detect(&self, req: &mut Request, path_state: &mut PathState) -> Option<DetectMatched> {
+        self.detect_indexed(None, req, path_state)
+    }
+
+    /// Detect current router with the help of a prebuilt [`RouterIndex`], children which can not
+    /// be matched by the path are skipped, the match priority is the same as [`Router::detect`].
+    pub(crate) fn detect_indexed(
+        &self,
+        index: Option<&RouterIndex>,
+        req: &mut Request,
+        path_state: &mut PathState,
+    ) -> Option<DetectMatched> {
         for filter in &self.filters {
             if !filter.filter(req, path_state) {
                 return None;
             }
         }
+        if let Some(catcher) = &self.catcher {
+            let row = path_state.cursor.0;
+            if path_state.catcher.as_ref().map(|(r, _)| row >= *r).unwrap_or(true) {
+                path_state.catcher = Some((row, catcher.clone()));
+            }
+        }
         if !self.routers.is_empty() {
             let original_cursor = path_state.cursor;
-            for child in &self.routers {
-                if let Some(dm) = child.detect(req, path_state) {
-                    return Some(DetectMatched {
-                        hoops: [&self.hoops[..], &dm.hoops[..]].concat(),
-                        handler: dm.handler.clone(),
-                    });
-                } else {
+            let candidates = index.map(|index| index.candidates(path_state));
+            let mut detect_child = |child: &Router, index: Option<&RouterIndex>| {
+                let dm = child.detect_indexed(index, req, path_state);
+                if dm.is_none() {
                     path_state.cursor = original_cursor;
                 }
+                dm
+            };
+            let dm = match (index, candidates) {
+                (Some(index), Some(candidates)) => candidates
+                    .into_iter()
+                    .find_map(|i| detect_child(&self.routers[i], index.children.get(i))),
+                _ => self.routers.iter().find_map(|child| detect_child(child, None)),
+            };
+            if let Some(dm) = dm {
+                return Some(DetectMatched {
+                    hoops: [&self.hoops[..], &dm.hoops[..]].concat(),
+                    handler: dm.handler,
+                    catcher: dm.catcher.or_else(|| self.catcher.clone()),
+                });
             }
         }
-        if let Some(handler) = self.handler.clone() {
-            if path_state.ended() {
+        if path_state.ended() {
+            path_state.once_ended = true;
+            if let Some(handler) = self.handler.clone() {
                 return Some(DetectMatched {
                     hoops: self.hoops.clone(),
                     handler,
+                    catcher: self.catcher.clone(),
                 });
             }
         }
         None
     }
 
+    /// Detect which methods are allowed for the request's path.
+    ///
+    /// The request is detected again with every common method except its own one,
+    /// the methods which can be matched are returned. Request's method is restored after detecting.
+    pub fn allowed_methods(&self, req: &mut Request) -> Vec<Method> {
+        self.allowed_methods_indexed(None, req)
+    }
+
+    pub(crate) fn allowed_methods_indexed(&self, index: Option<&RouterIndex>, req: &mut Request) -> Vec<Method> {
+        let original_method = req.method().clone();
+        let mut methods = Vec::new();
+        for method in COMMON_METHODS {
+            if method == original_method {
+                continue;
+            }
+            *req.method_mut() = method.clone();
+            let mut path_state = PathState::new(req.uri().path());
+            if self.detect_indexed(index, req, &mut path_state).is_some() {
+                methods.push(method);
+            }
+        }
+        *req.method_mut() = original_method;
+        methods
+    }
+
+    /// Get the flattened route table of current router and it's descendants.
+    ///
+    /// Each router with handler is returned as a [`RouteInfo`], the filters of it and all it's ancestors
+    /// are merged by [`FilterInfo::and`]. Routes are returned in the order of match priority, the routes of
+    /// services mounted by [`Router::mount`] are included with the mount prefix. Routes which no method can
+    /// reach, like `get().and(post())`, are skipped.
+    pub fn routes(&self) -> impl Iterator<Item = RouteInfo> {
+        let mut routes = Vec::new();
+        self.collect_routes(FilterInfo::default(), &[], &mut routes);
+        routes.into_iter()
+    }
+    fn collect_routes(&self, info: FilterInfo, hoops: &[&'static str], routes: &mut Vec<RouteInfo>) {
+        let info = self.filters.iter().fold(info, |info, filter| info.and(filter.info()));
+        let hoops = hoops
+            .iter()
+            .copied()
+            .chain(self.hoops.iter().map(|hoop| hoop.type_name()))
+            .collect::<Vec<_>>();
+        for child in &self.routers {
+            child.collect_routes(info.clone(), &hoops, routes);
+        }
+        if let Some(handler) = &self.handler {
+            // The routes of a mounted service are listed under the mount prefix.
+            if let Some(service) = handler.as_mounted_service() {
+                service.router().collect_routes(info, &hoops, routes);
+                return;
+            }
+            if matches!(&info.methods, Some(methods) if methods.is_empty()) {
+                return;
+            }
+            routes.push(RouteInfo {
+                path: format!("/{}", info.path.unwrap_or_default()),
+                methods: info.methods.unwrap_or_default(),
+                host: info.host,
+                scheme: info.scheme,
+                port: info.port,
+                name: self.name.clone(),
+                hoops,
+                handler: handler.type_name(),
+                handler_type_id: handler.type_id(),
+            });
+        }
+    }
+
     /// Push a router as child of current router.
     #[inline]
     pub fn push(mut self, router: Router) -> Self {
         self.routers.push(router);
         self
     }
+    /// Mount a [`Service`] at the path prefix as child of current router.
+    ///
+    /// All requests which match the prefix are passed to the service, the matched prefix is stripped
+    /// from request's uri, so the service sees paths relative to it's mount point, and the stripped prefix
+    /// can be got by [`Request::mount_prefix`]. The mounted service uses it's own catcher.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// #[handler]
+    /// async fn list_users(req: &mut Request) -> String {
+    ///     format!("{}{}", req.mount_prefix(), req.uri().path())
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let admin = Router::with_path("users").get(list_users);
+    /// let router = Router::new().mount("admin", admin);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn mount(self, prefix: impl Into<String>, service: impl Into<Service>) -> Self {
+        let prefix = prefix.into();
+        let router = if prefix.trim_matches('/').is_empty() {
+            Router::new()
+        } else {
+            Router::with_path(prefix)
+        };
+        self.push(
+            router
+                .filter(MountFilter)
+                .handle(MountedService(Arc::new(service.into()))),
+        )
+    }
     /// Append all routers in a Vec as children of current router.
     #[inline]
     pub fn append(mut self, others: &mut Vec<Router>) -> Self {
@@ -289,6 +494,41 @@ impl Router {
         self
     }
 
+    /// Sets current router's name, it can be used to generate url by [`Service::url_for`].
+    ///
+    /// [`Service::url_for`]: crate::Service::url_for
+    #[inline]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets current router's catcher, it is used to catch errors produced in current router or it's descendants.
+    ///
+    /// For the request not matched by any router, the catcher of the deepest router whose filters are matched
+    /// is used. If no router's catcher is found, the catcher of [`Service`] is used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # use salvo_core::catcher::Catcher;
+    /// #[handler]
+    /// async fn api_error(res: &mut Response, ctrl: &mut FlowCtrl) {
+    ///     if let Some(code) = res.status_code {
+    ///         res.render(Json(code.as_u16()));
+    ///         ctrl.skip_rest();
+    ///     }
+    /// }
+    ///
+    /// let router = Router::new().push(Router::with_path("api").catcher(Catcher::default().hoop(api_error)));
+    /// ```
+    #[inline]
+    pub fn catcher(mut self, catcher: impl Into<Arc<Catcher>>) -> Self {
+        self.catcher = Some(catcher.into());
+        self
+    }
+
     /// When you want write router chain, this function will be useful,
     /// You can write your custom logic in FnOnce.
     #[inline]
@@ -438,8 +678,9 @@ impl fmt::Debug for Router {
 
 #[cfg(test)]
 mod tests {
-    use super::{PathState, Router};
+    use super::{filter, Filter, PathState, Router};
     use crate::handler;
+    use crate::http::Method;
     use crate::test::TestClient;
     use crate::Response;
 
@@ -487,6 +728,39 @@ mod tests {
         );
     }
     #[test]
+    fn test_router_routes() {
+        #[handler]
+        async fn fake_hoop(_res: &mut Response) {}
+        let router = Router::new().host("localhost").push(
+            Router::with_path("users").hoop(fake_hoop).get(fake_handler).push(
+                Router::with_path("<id>")
+                    .name("user.show")
+                    .filter(filter::get().or(filter::head()))
+                    .handle(fake_handler),
+            ),
+        );
+        // Unreachable route is skipped.
+        let router = router.push(Router::with_path("never").filter(filter::get()).post(fake_handler));
+        let routes = router.routes().collect::<Vec<_>>();
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].path, "/users");
+        assert_eq!(routes[0].methods, vec![Method::GET]);
+        assert_eq!(routes[0].host.as_deref(), Some("localhost"));
+        assert_eq!(
+            routes[0].hoops,
+            vec!["salvo_core::routing::router::tests::test_router_routes::fake_hoop"]
+        );
+        assert_eq!(routes[0].handler, "salvo_core::routing::router::tests::fake_handler");
+        assert_eq!(routes[1].path, "/users/<id>");
+        assert_eq!(routes[1].methods, vec![Method::GET, Method::HEAD]);
+        assert_eq!(routes[1].name.as_deref(), Some("user.show"));
+        assert_eq!(
+            routes[0].to_string(),
+            "GET localhost/users [salvo_core::routing::router::tests::test_router_routes::fake_hoop] \
+            -> salvo_core::routing::router::tests::fake_handler"
+        );
+    }
+    #[test]
     fn test_router_detect1() {
         let router = Router::default().push(
             Router::with_path("users")
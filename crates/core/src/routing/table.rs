@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::json;
+
+use super::{CompiledRouter, RouteInfo};
+use crate::http::{guess_accept_mime, Request, Response};
+use crate::writer::Text;
+use crate::{Depot, FlowCtrl, Handler};
+
+/// Router served by the outermost [`Service`](crate::Service) of current request, it is injected into depot
+/// for [`RouteTable`].
+pub(crate) struct ServedRouter(pub(crate) Arc<CompiledRouter>);
+
+/// `RouteTable` is a handler which lists all routes of the router being served, it is useful for debugging.
+///
+/// The table is built when the request is handled, so routes added before the service is created and
+/// routers swapped by [`ServiceSwapper`](crate::ServiceSwapper) are listed. If the service is mounted,
+/// routes of the outermost service are listed.
+///
+/// Each route is listed with it's methods, full path template, middlewares and handler. The routes
+/// are rendered as json if the request accepts `application/json`, otherwise as plain text.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::routing::RouteTable;
+///
+/// #[handler]
+/// async fn hello() -> &'static str {
+///     "Hello World"
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let router = Router::new()
+///     .push(Router::with_path("hello").get(hello))
+///     .push(Router::with_path("_routes").get(RouteTable::new()));
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RouteTable;
+impl RouteTable {
+    /// Create new `RouteTable`.
+    #[inline]
+    pub fn new() -> Self {
+        RouteTable
+    }
+    /// Get the routes of the router serving current request.
+    #[inline]
+    pub fn routes(depot: &Depot) -> Vec<RouteInfo> {
+        depot
+            .obtain::<ServedRouter>()
+            .map(|served| served.0.router().routes().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl Handler for RouteTable {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, _ctrl: &mut FlowCtrl) {
+        let routes = RouteTable::routes(depot);
+        if guess_accept_mime(req, None).subtype() == mime::JSON {
+            let routes = routes
+                .iter()
+                .map(|route| {
+                    json!({
+                        "methods": route.methods.iter().map(|m| m.as_str()).collect::<Vec<_>>(),
+                        "path": route.path,
+                        "host": route.host,
+                        "scheme": route.scheme.as_ref().map(|s| s.as_str()),
+                        "port": route.port,
+                        "name": route.name,
+                        "hoops": route.hoops,
+                        "handler": route.handler,
+                    })
+                })
+                .collect::<Vec<_>>();
+            res.render(Text::Json(serde_json::Value::Array(routes).to_string()));
+        } else {
+            let routes = routes.iter().map(|route| route.to_string()).collect::<Vec<_>>();
+            res.render(Text::Plain(routes.join("\n")));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::routing::RouteTable;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[handler]
+    async fn hello() -> &'static str {
+        "Hello World"
+    }
+
+    #[tokio::test]
+    async fn test_route_table() {
+        let router = Router::new()
+            .push(Router::with_path("users/<id>").get(hello).delete(hello))
+            .push(Router::with_path("_routes").get(RouteTable::new()));
+        let service = Service::new(router);
+
+        let content = TestClient::get("http://127.0.0.1:5800/_routes")
+            .add_header("accept", "text/plain", true)
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(
+            content,
+            "GET /users/<id> -> salvo_core::routing::table::tests::hello\n\
+            DELETE /users/<id> -> salvo_core::routing::table::tests::hello\n\
+            GET /_routes -> salvo_core::routing::table::RouteTable"
+        );
+
+        let content = TestClient::get("http://127.0.0.1:5800/_routes")
+            .add_header("accept", "application/json", true)
+            .send(&service)
+            .await
+            .take_json::<serde_json::Value>()
+            .await
+            .unwrap();
+        assert_eq!(content[1]["methods"][0], "DELETE");
+        assert_eq!(content[1]["path"], "/users/<id>");
+
+        // Swapped router is listed.
+        service.swapper().swap_router(
+            Router::new()
+                .push(Router::with_path("posts").get(hello))
+                .push(Router::with_path("_routes").get(RouteTable::new())),
+        );
+        let content = TestClient::get("http://127.0.0.1:5800/_routes")
+            .add_header("accept", "text/plain", true)
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(
+            content,
+            "GET /posts -> salvo_core::routing::table::tests::hello\n\
+            GET /_routes -> salvo_core::routing::table::RouteTable"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_route_table_with_mounted_service() {
+        let admin = Router::new()
+            .push(Router::with_path("posts/<id>").get(hello))
+            .push(Router::with_path("_routes").get(RouteTable::new()));
+        let router = Router::new()
+            .push(Router::with_path("users").get(hello))
+            .mount("admin", Service::new(admin))
+            .mount("/", Router::with_path("about").get(hello));
+        let service = Service::new(router);
+
+        let content = TestClient::get("http://127.0.0.1:5800/admin/_routes")
+            .add_header("accept", "application/json", true)
+            .send(&service)
+            .await
+            .take_json::<serde_json::Value>()
+            .await
+            .unwrap();
+        let routes = content
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|route| route["path"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(routes, vec!["/users", "/admin/posts/<id>", "/admin/_routes", "/about"]);
+        assert_eq!(content[1]["handler"], "salvo_core::routing::table::tests::hello");
+    }
+}
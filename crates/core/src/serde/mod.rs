@@ -8,6 +8,8 @@ use serde::de::{
 };
 use serde::forward_to_deserialize_any;
 
+use crate::http::form::{with_deserializing_file, FilePart, FILE_PART_NAME};
+
 mod request;
 pub use request::from_request;
 
@@ -342,6 +344,71 @@ where
     }
 }
 
+/// Files of a multipart form field, a single [`FilePart`] takes the first one.
+///
+/// The files are handed to [`FilePart`]'s `Deserialize` implementation as they are, so only the files of the
+/// request can be extracted.
+#[derive(Clone, Copy)]
+struct FilesValue<'de>(&'de [FilePart]);
+
+impl<'de> IntoDeserializer<'de, ValError> for FilesValue<'de> {
+    type Deserializer = Self;
+
+    #[inline]
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for FilesValue<'de> {
+    type Error = ValError;
+
+    #[inline]
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(DeError::custom("file part can only be extracted as `FilePart`"))
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if name == FILE_PART_NAME {
+            let file = self
+                .0
+                .first()
+                .ok_or_else(|| DeError::custom("expected vec not empty"))?;
+            with_deserializing_file(file, || visitor.visit_newtype_struct(().into_deserializer()))
+        } else {
+            visitor.visit_newtype_struct(self)
+        }
+    }
+
+    #[inline]
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(SeqDeserializer::new(self.0.chunks(1).map(FilesValue)))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple_struct struct identifier tuple ignored_any map enum
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
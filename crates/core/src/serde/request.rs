@@ -1,6 +1,10 @@
 use std::borrow::Cow;
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Formatter};
 use std::iter::Iterator;
+use std::rc::Rc;
 
 use indexmap::IndexMap;
 use multimap::MultiMap;
@@ -11,12 +15,13 @@ use serde_json::value::RawValue;
 
 use crate::extract::metadata::{Field, Source, SourceFormat, SourceFrom};
 use crate::extract::Metadata;
-use crate::http::form::FormData;
+use crate::http::errors::InvalidParam;
+use crate::http::form::{FilePart, FormData};
 use crate::http::header::HeaderMap;
 use crate::http::ParseError;
 use crate::Request;
 
-use super::{CowValue, VecValue};
+use super::{CowValue, FilesValue, VecValue};
 
 pub async fn from_request<'de, T>(req: &'de mut Request, metadata: &'de Metadata) -> Result<T, ParseError>
 where
@@ -25,22 +30,107 @@ where
     // Ensure body is parsed correctly.
     req.form_data().await.ok();
     req.payload().await.ok();
-    Ok(T::deserialize(RequestDeserializer::new(req, metadata)?)?)
+    let deserializer = RequestDeserializer::new(req, metadata)?;
+    let invalid_params = deserializer.invalid_params.clone();
+    T::deserialize(deserializer).map_err(|e| {
+        let mut params = invalid_params.take();
+        if params.is_empty() {
+            // Missing field error is returned after all fields are visited.
+            if let Some(name) = e.missing_field {
+                params.push(InvalidParam::new(name, e.to_string()));
+            }
+        }
+        if params.is_empty() {
+            ParseError::Deserialize(e.inner)
+        } else {
+            ParseError::Extract {
+                source: e.inner,
+                params,
+            }
+        }
+    })
+}
+
+/// Error of [`RequestDeserializer`], the missing field is kept when the error is created by the deserialized type.
+#[derive(Debug)]
+pub(crate) struct RequestError {
+    inner: ValError,
+    missing_field: Option<&'static str>,
+}
+impl de::Error for RequestError {
+    #[inline]
+    fn custom<T: Display>(msg: T) -> Self {
+        ValError::custom(msg).into()
+    }
+    #[inline]
+    fn missing_field(field: &'static str) -> Self {
+        RequestError {
+            inner: ValError::missing_field(field),
+            missing_field: Some(field),
+        }
+    }
+}
+impl From<ValError> for RequestError {
+    #[inline]
+    fn from(inner: ValError) -> Self {
+        RequestError {
+            inner,
+            missing_field: None,
+        }
+    }
+}
+impl Display for RequestError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.inner, f)
+    }
 }
+impl StdError for RequestError {}
 
 #[derive(Clone, Debug)]
 pub(crate) enum Payload<'a> {
     FormData(&'a FormData),
     JsonStr(&'a str),
     JsonMap(HashMap<&'a str, &'a RawValue>),
+    /// Fields decoded from MessagePack, CBOR or xml body, with the format of the body.
+    #[cfg(any(feature = "msgpack", feature = "cbor", feature = "xml"))]
+    Decoded(SourceFormat, Rc<HashMap<String, serde_json::Value>>),
+}
+
+/// Decode the fields of MessagePack, CBOR or xml body, returns `None` if the body is not in these formats.
+///
+/// Xml body is decoded as a flat map of the child elements of the root element, so the values of xml fields
+/// are always strings, nested elements and repeated elements are not supported.
+#[cfg(any(feature = "msgpack", feature = "cbor", feature = "xml"))]
+fn decode_payload<'a>(ctype: &crate::http::Mime, data: &[u8]) -> Result<Option<Payload<'a>>, ParseError> {
+    use serde_json::Value;
+
+    #[cfg(feature = "msgpack")]
+    if crate::http::request::is_msgpack(ctype) {
+        let fields = rmp_serde::from_slice::<HashMap<String, Value>>(data)?;
+        return Ok(Some(Payload::Decoded(SourceFormat::MsgPack, Rc::new(fields))));
+    }
+    #[cfg(feature = "cbor")]
+    if crate::http::request::is_cbor(ctype) {
+        let fields = ciborium::de::from_reader::<HashMap<String, Value>, _>(data)?;
+        return Ok(Some(Payload::Decoded(SourceFormat::Cbor, Rc::new(fields))));
+    }
+    #[cfg(feature = "xml")]
+    if crate::http::request::is_xml(ctype) {
+        let fields = quick_xml::de::from_str::<HashMap<String, String>>(std::str::from_utf8(data)?)?;
+        let fields = fields.into_iter().map(|(k, v)| (k, Value::String(v))).collect();
+        return Ok(Some(Payload::Decoded(SourceFormat::Xml, Rc::new(fields))));
+    }
+    Ok(None)
 }
 
-#[derive(Debug)]
 pub(crate) struct RequestDeserializer<'de> {
     params: &'de IndexMap<String, String>,
     queries: &'de MultiMap<String, String>,
     #[cfg(feature = "cookie")]
     cookies: &'de cookie::CookieJar,
+    #[cfg(feature = "cookie")]
+    cookie_key: Option<&'de cookie::Key>,
     headers: &'de HeaderMap,
     payload: Option<Payload<'de>>,
     metadata: &'de Metadata,
@@ -48,6 +138,10 @@ pub(crate) struct RequestDeserializer<'de> {
     field_source: Option<&'de Source>,
     field_str_value: Option<&'de str>,
     field_vec_value: Option<Vec<CowValue<'de>>>,
+    field_file_value: Option<&'de Vec<FilePart>>,
+    #[cfg(any(feature = "msgpack", feature = "cbor", feature = "xml"))]
+    field_decoded_value: Option<serde_json::Value>,
+    invalid_params: Rc<RefCell<Vec<InvalidParam>>>,
 }
 
 impl<'de> RequestDeserializer<'de> {
@@ -71,6 +165,13 @@ impl<'de> RequestDeserializer<'de> {
                         };
                     }
                 }
+                #[cfg(any(feature = "msgpack", feature = "cbor", feature = "xml"))]
+                _ => {
+                    if let Some(data) = request.payload.get() {
+                        payload = decode_payload(&ctype, data)?;
+                    }
+                }
+                #[cfg(not(any(feature = "msgpack", feature = "cbor", feature = "xml")))]
                 _ => {}
             }
         }
@@ -80,16 +181,46 @@ impl<'de> RequestDeserializer<'de> {
             headers: request.headers(),
             #[cfg(feature = "cookie")]
             cookies: request.cookies(),
+            #[cfg(feature = "cookie")]
+            cookie_key: request.cookie_key(),
             payload,
             metadata,
             field_index: -1,
             field_source: None,
             field_str_value: None,
             field_vec_value: None,
+            field_file_value: None,
+            #[cfg(any(feature = "msgpack", feature = "cbor", feature = "xml"))]
+            field_decoded_value: None,
+            invalid_params: Rc::new(RefCell::new(vec![])),
         })
     }
     #[inline]
-    fn deserialize_value<T>(&mut self, seed: T) -> Result<T::Value, ValError>
+    fn deserialize_value<T>(&mut self, seed: T) -> Result<T::Value, RequestError>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        let recorded = self.invalid_params.borrow().len();
+        let field = self.metadata.fields.get(self.field_index as usize);
+        let source = self.field_source.map(|source| source.from);
+        let result = self.deserialize_field_value(seed);
+        if let (Err(e), Some(field)) = (&result, field) {
+            let mut params = self.invalid_params.borrow_mut();
+            // The nested extractible type has recorded it's own invalid fields.
+            if params.len() == recorded {
+                let name = e
+                    .missing_field
+                    .map(String::from)
+                    .unwrap_or_else(|| self.metadata.request_name(field).into_owned());
+                let mut param = InvalidParam::new(name, e.to_string());
+                param.source = source;
+                params.push(param);
+            }
+        }
+        result
+    }
+    #[inline]
+    fn deserialize_field_value<T>(&mut self, seed: T) -> Result<T::Value, RequestError>
     where
         T: de::DeserializeSeed<'de>,
     {
@@ -105,7 +236,7 @@ impl<'de> RequestDeserializer<'de> {
             let mut value = serde_json::Deserializer::new(serde_json::de::StrRead::new(value));
 
             seed.deserialize(&mut value)
-                .map_err(|_| ValError::custom("parse value error"))
+                .map_err(|_| RequestError::custom("parse value error"))
         } else if source.from == SourceFrom::Request {
             let field = self
                 .metadata
@@ -119,19 +250,73 @@ impl<'de> RequestDeserializer<'de> {
                 headers: self.headers,
                 #[cfg(feature = "cookie")]
                 cookies: self.cookies,
+                #[cfg(feature = "cookie")]
+                cookie_key: self.cookie_key,
                 payload: self.payload.clone(),
                 metadata,
                 field_index: -1,
                 field_source: None,
                 field_str_value: None,
                 field_vec_value: None,
+                field_file_value: None,
+                #[cfg(any(feature = "msgpack", feature = "cbor", feature = "xml"))]
+                field_decoded_value: None,
+                invalid_params: self.invalid_params.clone(),
             })
         } else if let Some(value) = self.field_str_value.take() {
-            seed.deserialize(CowValue(value.into()))
+            Ok(seed.deserialize(CowValue(value.into()))?)
         } else if let Some(value) = self.field_vec_value.take() {
-            seed.deserialize(VecValue(value.into_iter()))
+            Ok(seed.deserialize(VecValue(value.into_iter()))?)
+        } else if let Some(value) = self.take_decoded_value() {
+            seed.deserialize(value).map_err(RequestError::custom)
+        } else if let Some(files) = self.field_file_value.take() {
+            Ok(seed.deserialize(FilesValue(files))?)
         } else {
-            Err(ValError::custom("parse value error"))
+            Err(RequestError::custom("parse value error"))
+        }
+    }
+
+    #[cfg(any(feature = "msgpack", feature = "cbor", feature = "xml"))]
+    #[inline]
+    fn take_decoded_value(&mut self) -> Option<serde_json::Value> {
+        self.field_decoded_value.take()
+    }
+    #[cfg(not(any(feature = "msgpack", feature = "cbor", feature = "xml")))]
+    #[inline]
+    fn take_decoded_value(&mut self) -> Option<serde_json::Value> {
+        None
+    }
+    /// Fill value from the decoded fields if the body is in the same format as the source.
+    #[cfg(any(feature = "msgpack", feature = "cbor", feature = "xml"))]
+    #[inline]
+    fn fill_decoded_value(&mut self, field: &'de Field, field_name: &str, source: &'de Source) -> bool {
+        if let Some(Payload::Decoded(format, fields)) = &self.payload {
+            if *format != source.format {
+                return false;
+            }
+            let mut value = fields.get(field_name);
+            if value.is_none() {
+                for alias in &field.aliases {
+                    value = fields.get(*alias);
+                    if value.is_some() {
+                        break;
+                    }
+                }
+            }
+            match value {
+                // Strings are parsed as the field's type, so xml fields are able to be numbers.
+                Some(serde_json::Value::String(value)) => {
+                    self.field_vec_value = Some(vec![CowValue(Cow::Owned(value.clone()))]);
+                }
+                Some(value) => {
+                    self.field_decoded_value = Some(value.clone());
+                }
+                None => return false,
+            }
+            self.field_source = Some(source);
+            true
+        } else {
+            false
         }
     }
 
@@ -146,20 +331,7 @@ impl<'de> RequestDeserializer<'de> {
             return false;
         };
 
-        let field_name: Cow<'_, str> = if let Some(rename_all) = self.metadata.rename_all {
-            if let Some(rename) = field.rename {
-                Cow::from(rename)
-            } else {
-                rename_all.rename(field.name).into()
-            }
-        } else {
-            if let Some(rename) = field.rename {
-                rename
-            } else {
-                field.name
-            }
-            .into()
-        };
+        let field_name = self.metadata.request_name(field);
 
         for source in sources {
             match source.from {
@@ -223,6 +395,32 @@ impl<'de> RequestDeserializer<'de> {
                     }
                 }
                 #[cfg(feature = "cookie")]
+                SourceFrom::Cookie if matches!(source.format, SourceFormat::Signed | SourceFormat::Private) => {
+                    let key = match self.cookie_key {
+                        Some(key) => key,
+                        None => {
+                            tracing::error!(
+                                "cookie key is not set, signed or private cookie {} is ignored",
+                                field.name
+                            );
+                            continue;
+                        }
+                    };
+                    let get = |name: &str| {
+                        if source.format == SourceFormat::Signed {
+                            self.cookies.signed(key).get(name)
+                        } else {
+                            self.cookies.private(key).get(name)
+                        }
+                    };
+                    let cookie = get(field_name.as_ref()).or_else(|| field.aliases.iter().find_map(|alias| get(alias)));
+                    if let Some(cookie) = cookie {
+                        self.field_vec_value = Some(vec![CowValue(Cow::Owned(cookie.value().to_owned()))]);
+                        self.field_source = Some(source);
+                        return true;
+                    }
+                }
+                #[cfg(feature = "cookie")]
                 SourceFrom::Cookie => {
                     let mut value = None;
                     if let Some(cookie) = self.cookies.get(field_name.as_ref()) {
@@ -286,6 +484,10 @@ impl<'de> RequestDeserializer<'de> {
                                     self.field_source = Some(source);
                                     return true;
                                 }
+                                #[cfg(any(feature = "msgpack", feature = "cbor", feature = "xml"))]
+                                Payload::Decoded(..) => {
+                                    return false;
+                                }
                             }
                         } else {
                             return false;
@@ -306,6 +508,20 @@ impl<'de> RequestDeserializer<'de> {
                                 self.field_vec_value = Some(value.iter().map(|v| CowValue(Cow::from(v))).collect());
                                 self.field_source = Some(source);
                                 return true;
+                            }
+                            let mut files = form_data.files.get_vec(field.name);
+                            if files.is_none() {
+                                for alias in &field.aliases {
+                                    files = form_data.files.get_vec(*alias);
+                                    if files.is_some() {
+                                        break;
+                                    }
+                                }
+                            }
+                            if let Some(files) = files {
+                                self.field_file_value = Some(files);
+                                self.field_source = Some(source);
+                                return true;
                             } else {
                                 return false;
                             }
@@ -313,6 +529,24 @@ impl<'de> RequestDeserializer<'de> {
                             return false;
                         }
                     }
+                    #[cfg(feature = "msgpack")]
+                    SourceFormat::MsgPack => {
+                        if self.fill_decoded_value(field, field_name.as_ref(), source) {
+                            return true;
+                        }
+                    }
+                    #[cfg(feature = "cbor")]
+                    SourceFormat::Cbor => {
+                        if self.fill_decoded_value(field, field_name.as_ref(), source) {
+                            return true;
+                        }
+                    }
+                    #[cfg(feature = "xml")]
+                    SourceFormat::Xml => {
+                        if self.fill_decoded_value(field, field_name.as_ref(), source) {
+                            return true;
+                        }
+                    }
                     _ => {
                         panic!("Unsupported source format: {:?}", source.format);
                     }
@@ -328,6 +562,11 @@ impl<'de> RequestDeserializer<'de> {
             let field = &self.metadata.fields[self.field_index as usize];
             self.field_str_value = None;
             self.field_vec_value = None;
+            self.field_file_value = None;
+            #[cfg(any(feature = "msgpack", feature = "cbor", feature = "xml"))]
+            {
+                self.field_decoded_value = None;
+            }
 
             if self.fill_value(field) {
                 return Some(Cow::from(field.name));
@@ -338,7 +577,7 @@ impl<'de> RequestDeserializer<'de> {
 }
 
 impl<'de> de::Deserializer<'de> for RequestDeserializer<'de> {
-    type Error = ValError;
+    type Error = RequestError;
 
     #[inline]
     fn deserialize_any<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
@@ -364,7 +603,7 @@ impl<'de> de::Deserializer<'de> for RequestDeserializer<'de> {
 }
 
 impl<'de> de::MapAccess<'de> for RequestDeserializer<'de> {
-    type Error = ValError;
+    type Error = RequestError;
 
     #[inline]
     fn next_key_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
@@ -393,7 +632,7 @@ impl<'de> de::MapAccess<'de> for RequestDeserializer<'de> {
     {
         match self.next() {
             Some(key) => {
-                let key = kseed.deserialize(key.into_deserializer())?;
+                let key = kseed.deserialize(IntoDeserializer::<RequestError>::into_deserializer(key))?;
                 let value = self.deserialize_value(vseed)?;
                 Ok(Some((key, value)))
             }
@@ -406,6 +645,7 @@ impl<'de> de::MapAccess<'de> for RequestDeserializer<'de> {
 mod tests {
     use serde::{Deserialize, Serialize};
 
+    use super::{InvalidParam, ParseError};
     use crate::macros::Extractible;
     use crate::test::TestClient;
 
@@ -431,6 +671,27 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_de_request_missing_field() {
+        #[derive(Deserialize, Extractible, Eq, PartialEq, Debug)]
+        #[extract(default_source(from = "query"))]
+        struct RequestData {
+            page: u32,
+            #[serde(rename = "page_size")]
+            size: u32,
+        }
+        let mut req = TestClient::get("http://127.0.0.1:5800/test").query("page", "1").build();
+        match req.extract::<RequestData>().await {
+            Err(ParseError::Extract { params, .. }) => {
+                assert_eq!(
+                    params,
+                    vec![InvalidParam::new("page_size", "missing field `page_size`")]
+                );
+            }
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn test_de_request_with_lifetime() {
         #[derive(Deserialize, Extractible, Eq, PartialEq, Debug)]
@@ -619,4 +880,196 @@ mod tests {
             }
         );
     }
+
+    #[cfg(feature = "cookie")]
+    #[tokio::test]
+    async fn test_de_request_with_cookies() {
+        use cookie::{Cookie, CookieJar, Key};
+
+        #[derive(Deserialize, Extractible, Eq, PartialEq, Debug)]
+        #[extract(default_source(from = "cookie"))]
+        struct RequestData {
+            theme: String,
+            #[extract(source(from = "cookie", format = "signed"))]
+            user_id: i64,
+            #[extract(source(from = "cookie", format = "private"), rename = "sid")]
+            session: String,
+            #[extract(source(from = "cookie", format = "signed"))]
+            role: Option<String>,
+        }
+
+        let key = Key::generate();
+        let mut jar = CookieJar::new();
+        jar.signed_mut(&key).add(Cookie::new("user_id", "12"));
+        jar.private_mut(&key).add(Cookie::new("sid", "abcd"));
+        let cookies = format!(
+            "theme=dark; {}; {}; role=admin",
+            jar.get("user_id").unwrap().encoded(),
+            jar.get("sid").unwrap().encoded()
+        );
+
+        let mut req = TestClient::get("http://127.0.0.1:5800/")
+            .add_header("cookie", &cookies, true)
+            .build();
+        assert!(req.extract::<RequestData>().await.is_err());
+
+        let mut req = TestClient::get("http://127.0.0.1:5800/")
+            .add_header("cookie", &cookies, true)
+            .build();
+        req.set_cookie_key(key);
+        let data: RequestData = req.extract().await.unwrap();
+        assert_eq!(
+            data,
+            RequestData {
+                theme: "dark".into(),
+                user_id: 12,
+                session: "abcd".into(),
+                role: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_de_request_with_files() {
+        use crate::http::form::FilePart;
+
+        #[derive(Deserialize, Extractible, Debug)]
+        #[extract(default_source(from = "body"))]
+        struct RequestData {
+            title: String,
+            cover: FilePart,
+            #[extract(alias = "attachment")]
+            attachments: Vec<FilePart>,
+            extra: Option<FilePart>,
+        }
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/upload")
+            .add_header("content-type", "multipart/form-data; boundary=--boundary", true)
+            .body(
+                "----boundary\r\n\
+Content-Disposition: form-data; name=\"title\"\r\n\r\nholiday\r\n\
+----boundary\r\n\
+Content-Disposition: form-data; name=\"cover\"; filename=\"cover.png\"\r\n\
+Content-Type: image/png\r\n\r\n\
+png\r\n\
+----boundary\r\n\
+Content-Disposition: form-data; name=\"attachment\"; filename=\"a.txt\"\r\n\
+Content-Type: text/plain\r\n\r\n\
+a\r\n\
+----boundary\r\n\
+Content-Disposition: form-data; name=\"attachment\"; filename=\"b.txt\"\r\n\
+Content-Type: text/plain\r\n\r\n\
+bb\r\n\
+----boundary--\r\n",
+            )
+            .build();
+        let data: RequestData = req.extract().await.unwrap();
+        assert_eq!(data.title, "holiday");
+        assert_eq!(data.cover.name(), Some("cover.png"));
+        assert_eq!(data.cover.content_type(), Some(mime::IMAGE_PNG));
+        assert_eq!(
+            data.attachments
+                .iter()
+                .map(|f| (f.name(), f.size()))
+                .collect::<Vec<_>>(),
+            [(Some("a.txt"), 1), (Some("b.txt"), 2)]
+        );
+        assert!(data.extra.is_none());
+
+        // Other data can not be deserialized to the files of alive uploads.
+        let path = data.cover.path().clone();
+        let forged = serde_json::json!({"name": null, "headers": [], "path": path, "size": 1});
+        assert!(serde_json::from_value::<FilePart>(forged).is_err());
+
+        // Files are shared by the request and the extracted data.
+        drop(req);
+        assert!(path.exists());
+        drop(data);
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert!(!path.exists());
+
+        assert!(serde_json::from_str::<FilePart>(r#""cover.png""#).is_err());
+        assert!(
+            serde_json::from_str::<FilePart>(r#"{"name":null,"headers":[],"path":"/etc/passwd","size":1}"#).is_err()
+        );
+    }
+
+    #[cfg(all(feature = "msgpack", feature = "xml"))]
+    #[tokio::test]
+    async fn test_de_request_with_msgpack_and_xml() {
+        #[derive(Deserialize, Extractible, Eq, PartialEq, Debug)]
+        #[extract(default_source(from = "body", format = "msgpack"))]
+        struct MsgPackData {
+            #[extract(source(from = "param"))]
+            id: i64,
+            name: String,
+            tags: Vec<String>,
+        }
+        #[derive(Deserialize, Extractible, Eq, PartialEq, Debug)]
+        #[extract(default_source(from = "body", format = "xml"))]
+        struct XmlData {
+            name: String,
+            age: u8,
+        }
+
+        #[derive(Serialize)]
+        struct Body {
+            name: &'static str,
+            tags: Vec<&'static str>,
+        }
+        let body = Body {
+            name: "jobs",
+            tags: vec!["apple", "next"],
+        };
+        let mut req = TestClient::post("http://127.0.0.1:5800/")
+            .add_header("content-type", "application/msgpack", true)
+            .body(rmp_serde::to_vec_named(&body).unwrap())
+            .build();
+        req.params.insert("id".into(), "1".into());
+        let data: MsgPackData = req.extract().await.unwrap();
+        assert_eq!(
+            data,
+            MsgPackData {
+                id: 1,
+                name: "jobs".into(),
+                tags: vec!["apple".into(), "next".into()],
+            }
+        );
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/")
+            .add_header("content-type", "application/xml", true)
+            .body("<user><name>jobs</name><age>56</age></user>")
+            .build();
+        let data: XmlData = req.extract().await.unwrap();
+        assert_eq!(
+            data,
+            XmlData {
+                name: "jobs".into(),
+                age: 56,
+            }
+        );
+
+        let mut req = TestClient::post("http://127.0.0.1:5800/")
+            .add_header("content-type", "application/xml", true)
+            .body("<user><name>jobs</name><age>56</age></user>")
+            .build();
+        req.params.insert("id".into(), "1".into());
+        assert!(req.extract::<MsgPackData>().await.is_err());
+
+        // Decode errors of the body are returned.
+        let mut req = TestClient::post("http://127.0.0.1:5800/")
+            .add_header("content-type", "application/msgpack", true)
+            .body(vec![0xc1])
+            .build();
+        req.params.insert("id".into(), "1".into());
+        assert!(matches!(
+            req.extract::<MsgPackData>().await,
+            Err(ParseError::SerdeMsgPack(_))
+        ));
+        let mut req = TestClient::post("http://127.0.0.1:5800/")
+            .add_header("content-type", "application/xml", true)
+            .body("<user><name>jobs</name><age>56</user>")
+            .build();
+        assert!(matches!(req.extract::<XmlData>().await, Err(ParseError::SerdeXml(_))));
+    }
 }
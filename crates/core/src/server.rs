@@ -1,15 +1,16 @@
 //! Server module
 use std::future::Future;
 use std::io::Result as IoResult;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 
 #[cfg(feature = "http1")]
 use hyper::server::conn::http1;
 #[cfg(feature = "http2")]
 use hyper::server::conn::http2;
-use tokio::sync::Notify;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use tokio::time::Duration;
+use tokio_util::sync::CancellationToken;
 
 #[cfg(feature = "quinn")]
 use crate::conn::quinn;
@@ -17,12 +18,200 @@ use crate::conn::{Accepted, Acceptor, Holding, HttpBuilders};
 use crate::http::{HeaderValue, HttpConnection, Version};
 use crate::Service;
 
+enum ServerCommand {
+    StopForcible,
+    StopGraceful(Option<Duration>),
+    Pause,
+    Resume,
+}
+
+/// Totals of a protocol since the server is started.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ProtocolStats {
+    /// Count of accepted connections.
+    pub connections: u64,
+    /// Count of handled requests.
+    pub requests: u64,
+}
+
+/// Snapshot of the counters of a [`Server`], it is returned by [`ServerHandle::stats`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ServerStats {
+    /// Count of alive connections.
+    pub alive_connections: usize,
+    /// Count of requests which are being handled.
+    pub in_flight_requests: usize,
+    /// Totals of HTTP/1.x.
+    pub http1: ProtocolStats,
+    /// Totals of HTTP/2.
+    pub http2: ProtocolStats,
+    /// Totals of HTTP/3.
+    pub http3: ProtocolStats,
+}
+
+#[derive(Default)]
+struct ProtocolCounters {
+    connections: AtomicU64,
+    requests: AtomicU64,
+}
+impl ProtocolCounters {
+    fn stats(&self) -> ProtocolStats {
+        ProtocolStats {
+            connections: self.connections.load(Ordering::Relaxed),
+            requests: self.requests.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// State shared by [`Server`], its handles and the connections it serves.
+pub(crate) struct ServerState {
+    holdings: Vec<Holding>,
+    paused: AtomicBool,
+    alive_connections: AtomicUsize,
+    in_flight_requests: AtomicUsize,
+    http1: ProtocolCounters,
+    http2: ProtocolCounters,
+    http3: ProtocolCounters,
+}
+impl ServerState {
+    fn new(holdings: Vec<Holding>) -> Self {
+        ServerState {
+            holdings,
+            paused: AtomicBool::new(false),
+            alive_connections: AtomicUsize::new(0),
+            in_flight_requests: AtomicUsize::new(0),
+            http1: ProtocolCounters::default(),
+            http2: ProtocolCounters::default(),
+            http3: ProtocolCounters::default(),
+        }
+    }
+
+    fn protocol(&self, version: Version) -> &ProtocolCounters {
+        match version {
+            Version::HTTP_2 => &self.http2,
+            Version::HTTP_3 => &self.http3,
+            _ => &self.http1,
+        }
+    }
+
+    /// Count a new request, the returned guard should be dropped when the request is handled.
+    pub(crate) fn start_request(self: &Arc<Self>, version: Version) -> InFlightGuard {
+        self.protocol(version).requests.fetch_add(1, Ordering::Relaxed);
+        self.in_flight_requests.fetch_add(1, Ordering::AcqRel);
+        InFlightGuard(self.clone())
+    }
+}
+
+pub(crate) struct InFlightGuard(Arc<ServerState>);
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.in_flight_requests.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Handle of a running [`Server`], it is returned by [`Server::handle`] and can be cloned freely.
+///
+/// # Example
+///
+/// ```no_run
+/// # use salvo_core::prelude::*;
+/// # use tokio::time::Duration;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let acceptor = TcpListener::new("127.0.0.1:5800").bind().await;
+/// let server = Server::new(acceptor);
+/// let handle = server.handle();
+/// tokio::spawn(async move {
+///     tokio::time::sleep(Duration::from_secs(60)).await;
+///     handle.stop_graceful(Duration::from_secs(30));
+/// });
+/// server.serve(Router::new()).await;
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ServerHandle {
+    tx_cmd: UnboundedSender<ServerCommand>,
+    state: Arc<ServerState>,
+}
+impl ServerHandle {
+    /// Stop the server at once, all the alive connections are dropped.
+    #[inline]
+    pub fn stop_forcible(&self) {
+        self.tx_cmd.send(ServerCommand::StopForcible).ok();
+    }
+
+    /// Stop the server gracefully.
+    ///
+    /// The server stops accepting and asks every alive connection to shut down once its in-flight requests are
+    /// done. The connections still alive after `timeout` are dropped, `None` means waiting for them forever.
+    #[inline]
+    pub fn stop_graceful(&self, timeout: impl Into<Option<Duration>>) {
+        self.tx_cmd.send(ServerCommand::StopGraceful(timeout.into())).ok();
+    }
+
+    /// Stop accepting new connections, the alive connections are still served.
+    ///
+    /// Incoming connections wait in the backlog of the listener until [`ServerHandle::resume`] is called.
+    #[inline]
+    pub fn pause(&self) {
+        self.state.paused.store(true, Ordering::Release);
+        self.tx_cmd.send(ServerCommand::Pause).ok();
+    }
+
+    /// Resume accepting new connections after [`ServerHandle::pause`].
+    #[inline]
+    pub fn resume(&self) {
+        self.state.paused.store(false, Ordering::Release);
+        self.tx_cmd.send(ServerCommand::Resume).ok();
+    }
+
+    /// Whether the server is paused.
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.state.paused.load(Ordering::Acquire)
+    }
+
+    /// Get holding information of the server.
+    #[inline]
+    pub fn holdings(&self) -> &[Holding] {
+        &self.state.holdings
+    }
+
+    /// Get the count of alive connections.
+    #[inline]
+    pub fn alive_connections(&self) -> usize {
+        self.state.alive_connections.load(Ordering::Acquire)
+    }
+
+    /// Get the count of requests which are being handled.
+    #[inline]
+    pub fn in_flight_requests(&self) -> usize {
+        self.state.in_flight_requests.load(Ordering::Acquire)
+    }
+
+    /// Get a snapshot of all the counters.
+    pub fn stats(&self) -> ServerStats {
+        ServerStats {
+            alive_connections: self.alive_connections(),
+            in_flight_requests: self.in_flight_requests(),
+            http1: self.state.http1.stats(),
+            http2: self.state.http2.stats(),
+            http3: self.state.http3.stats(),
+        }
+    }
+}
+
 /// HTTP Server
 ///
 /// A `Server` is created to listen on a port, parse HTTP requests, and hand them off to a [`Service`].
 pub struct Server<A> {
     acceptor: A,
     builders: HttpBuilders,
+    print_routes: bool,
+    tx_cmd: UnboundedSender<ServerCommand>,
+    rx_cmd: UnboundedReceiver<ServerCommand>,
+    state: Arc<ServerState>,
 }
 
 impl<A: Acceptor + Send> Server<A> {
@@ -41,6 +230,8 @@ impl<A: Acceptor + Send> Server<A> {
     /// ```
     #[inline]
     pub fn new(acceptor: A) -> Self {
+        let (tx_cmd, rx_cmd) = mpsc::unbounded_channel();
+        let state = Arc::new(ServerState::new(acceptor.holdings().to_vec()));
         Server {
             acceptor,
             builders: HttpBuilders {
@@ -51,9 +242,43 @@ impl<A: Acceptor + Send> Server<A> {
                 #[cfg(feature = "quinn")]
                 quinn: crate::conn::quinn::Builder,
             },
+            print_routes: false,
+            tx_cmd,
+            rx_cmd,
+            state,
         }
     }
 
+    /// Get a [`ServerHandle`] to stop the server and inspect its state after it is started.
+    #[inline]
+    pub fn handle(&self) -> ServerHandle {
+        ServerHandle {
+            tx_cmd: self.tx_cmd.clone(),
+            state: self.state.clone(),
+        }
+    }
+
+    /// Sets whether to print all routes of the served [`Service`] when server is starting.
+    ///
+    /// Each route is logged with its methods, full path template, middlewares and handler.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use salvo_core::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let acceptor = TcpListener::new("127.0.0.1:5800").bind().await;
+    /// Server::new(acceptor).print_routes(true);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn print_routes(mut self, print_routes: bool) -> Self {
+        self.print_routes = print_routes;
+        self
+    }
+
     /// Get holding information of this server.
     #[inline]
     pub fn holdings(&self) -> &[Holding] {
@@ -156,10 +381,18 @@ impl<A: Acceptor + Send> Server<A> {
         S: Into<Service> + Send,
         G: Future<Output = ()> + Send + 'static,
     {
-        let Self { mut acceptor, builders } = self;
-        let alive_connections = Arc::new(AtomicUsize::new(0));
-        let notify = Arc::new(Notify::new());
-        let timeout_notify = Arc::new(Notify::new());
+        let Self {
+            mut acceptor,
+            builders,
+            print_routes,
+            mut rx_cmd,
+            state,
+            ..
+        } = self;
+        // Every connection task holds a sender, so `recv` returns `None` once all of them are finished.
+        let (alive_tx, mut alive_rx) = mpsc::channel::<()>(1);
+        let graceful_stop_token = CancellationToken::new();
+        let force_stop_token = CancellationToken::new();
 
         tokio::pin!(signal);
 
@@ -178,55 +411,71 @@ impl<A: Acceptor + Send> Server<A> {
             }
         }
 
-        let service = Arc::new(service.into());
+        let service: Arc<Service> = Arc::new(service.into());
+        if print_routes {
+            for route in service.router().routes() {
+                tracing::info!("route {}", route);
+            }
+        }
         let builders = Arc::new(builders);
+        let stop_graceful = |timeout: Option<Duration>| {
+            if let Some(timeout) = timeout {
+                tracing::info!(timeout_in_seconds = timeout.as_secs_f32(), "initiate graceful shutdown",);
+                let force_stop_token = force_stop_token.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(timeout).await;
+                    force_stop_token.cancel();
+                });
+            } else {
+                tracing::info!("initiate graceful shutdown");
+            }
+            graceful_stop_token.cancel();
+        };
         loop {
+            let paused = state.paused.load(Ordering::Acquire);
             tokio::select! {
                 _ = &mut signal => {
-                    if let Some(timeout) = timeout {
-                        tracing::info!(
-                            timeout_in_seconds = timeout.as_secs_f32(),
-                            "initiate graceful shutdown",
-                        );
-
-                        let timeout_notify = timeout_notify.clone();
-                        tokio::spawn(async move {
-                            tokio::time::sleep(timeout).await;
-                            timeout_notify.notify_waiters();
-                        });
-                    } else {
-                        tracing::info!("initiate graceful shutdown");
+                    stop_graceful(timeout);
+                    break;
+                },
+                Some(cmd) = rx_cmd.recv() => {
+                    match cmd {
+                        ServerCommand::StopGraceful(timeout) => stop_graceful(timeout),
+                        ServerCommand::StopForcible => {
+                            tracing::info!("force shutdown");
+                            force_stop_token.cancel();
+                        }
+                        // The paused flag is read again in the next loop.
+                        ServerCommand::Pause | ServerCommand::Resume => continue,
                     }
                     break;
                 },
-                 accepted = acceptor.accept() => {
+                accepted = acceptor.accept(), if !paused => {
                     match accepted {
-                        Ok(Accepted { conn, local_addr, remote_addr, http_scheme, ..}) => {
+                        Ok(Accepted { mut conn, local_addr, remote_addr, http_scheme, http_version }) => {
                             let service = service.clone();
-                            let alive_connections = alive_connections.clone();
-                            let notify = notify.clone();
-                            let timeout_notify = timeout_notify.clone();
-                            let handler = service.hyper_handler(local_addr, remote_addr, http_scheme, alt_svc_h3.clone());
+                            let state = state.clone();
+                            let alive_tx = alive_tx.clone();
+                            let mut handler = service.hyper_handler(local_addr, remote_addr, http_scheme, alt_svc_h3.clone());
+                            handler.server_state = Some(state.clone());
                             let builders = builders.clone();
+                            let graceful_stop_token = graceful_stop_token.clone();
+                            let force_stop_token = force_stop_token.clone();
+                            state.alive_connections.fetch_add(1, Ordering::AcqRel);
                             tokio::spawn(async move {
-                                alive_connections.fetch_add(1, Ordering::SeqCst);
-                                let conn = conn.serve(handler, builders);
-                                if timeout.is_some() {
-                                    tokio::select! {
-                                        result = conn => {
-                                            if let Err(e) = result {
-                                                tracing::error!(error = ?e, "http serve connection failed");
-                                            }
-                                        },
-                                        _ = timeout_notify.notified() => {}
-                                    }
-                                } else if let Err(e) = conn.await {
-                                    tracing::error!(error = ?e, "http serve connection failed");
+                                let version = conn.version().await.unwrap_or(http_version);
+                                state.protocol(version).connections.fetch_add(1, Ordering::Relaxed);
+                                tokio::select! {
+                                    result = conn.serve(handler, builders, graceful_stop_token) => {
+                                        if let Err(e) = result {
+                                            tracing::error!(error = ?e, "http serve connection failed");
+                                        }
+                                    },
+                                    _ = force_stop_token.cancelled() => {}
                                 }
 
-                                if alive_connections.fetch_sub(1, Ordering::SeqCst) == 1 {
-                                    notify.notify_one();
-                                }
+                                state.alive_connections.fetch_sub(1, Ordering::AcqRel);
+                                drop(alive_tx);
                             });
                         },
                         Err(e) => {
@@ -236,10 +485,26 @@ impl<A: Acceptor + Send> Server<A> {
                 }
             }
         }
+        drop(acceptor);
+        drop(alive_tx);
 
-        if alive_connections.load(Ordering::SeqCst) > 0 {
+        if !force_stop_token.is_cancelled() && state.alive_connections.load(Ordering::Acquire) > 0 {
             tracing::info!("wait for all connections to close.");
-            notify.notified().await;
+        }
+        while !force_stop_token.is_cancelled() {
+            tokio::select! {
+                _ = alive_rx.recv() => break,
+                _ = force_stop_token.cancelled() => {
+                    tracing::info!("force close the alive connections");
+                }
+                Some(cmd) = rx_cmd.recv() => {
+                    // The drain can still be escalated by `ServerHandle::stop_forcible`.
+                    if let ServerCommand::StopForcible = cmd {
+                        tracing::info!("force shutdown");
+                        force_stop_token.cancel();
+                    }
+                }
+            }
         }
 
         tracing::info!("server stopped");
@@ -251,6 +516,7 @@ impl<A: Acceptor + Send> Server<A> {
 mod tests {
     use serde::Serialize;
 
+    use crate::conn::Acceptor;
     use crate::prelude::*;
     use crate::test::{ResponseExt, TestClient};
 
@@ -320,4 +586,164 @@ mod tests {
             .unwrap();
         assert!(result.contains("<code>404</code>"));
     }
+
+    #[tokio::test]
+    async fn test_stop_graceful_with_idle_connection() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::time::Duration;
+
+        #[handler]
+        async fn hello() -> &'static str {
+            "Hello World"
+        }
+        let acceptor = TcpListener::new("127.0.0.1:0").bind().await;
+        let addr = acceptor.holdings()[0].local_addr.clone().into_std().unwrap();
+        let server = Server::new(acceptor);
+        let handle = server.handle();
+        let server = tokio::spawn(server.serve(Router::new().get(hello)));
+
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut buf = [0; 1024];
+        let len = stream.read(&mut buf).await.unwrap();
+        assert!(String::from_utf8_lossy(&buf[..len]).ends_with("Hello World"));
+        assert_eq!(handle.alive_connections(), 1);
+
+        // The keep-alive connection is idle, it should be closed at once instead of holding the server open.
+        handle.stop_graceful(None);
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("server should stop")
+            .unwrap();
+        assert_eq!(stream.read(&mut buf).await.unwrap(), 0);
+        assert_eq!(handle.alive_connections(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_stats() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::time::Duration;
+
+        use crate::server::{ProtocolStats, ServerStats};
+
+        #[handler]
+        async fn hello() -> &'static str {
+            "Hello World"
+        }
+        let acceptor = TcpListener::new("127.0.0.1:0").bind().await;
+        let addr = acceptor.holdings()[0].local_addr.clone().into_std().unwrap();
+        let server = Server::new(acceptor);
+        let handle = server.handle();
+        assert_eq!(handle.holdings().len(), 1);
+        let server = tokio::spawn(server.serve(Router::new().get(hello)));
+
+        handle.pause();
+        assert!(handle.is_paused());
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut buf = [0; 1024];
+        assert!(tokio::time::timeout(Duration::from_millis(200), stream.read(&mut buf))
+            .await
+            .is_err());
+        assert_eq!(handle.alive_connections(), 0);
+
+        handle.resume();
+        let len = stream.read(&mut buf).await.unwrap();
+        assert!(String::from_utf8_lossy(&buf[..len]).ends_with("Hello World"));
+        assert_eq!(
+            handle.stats(),
+            ServerStats {
+                alive_connections: 1,
+                in_flight_requests: 0,
+                http1: ProtocolStats {
+                    connections: 1,
+                    requests: 1,
+                },
+                ..Default::default()
+            }
+        );
+
+        handle.stop_forcible();
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stop_graceful_after_connection_closed() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::time::Duration;
+
+        #[handler]
+        async fn slow() -> &'static str {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            "Slow"
+        }
+        #[handler]
+        async fn hang() -> &'static str {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            "Hang"
+        }
+        async fn wait_until(f: impl Fn() -> bool) {
+            while !f() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }
+        let acceptor = TcpListener::new("127.0.0.1:0").bind().await;
+        let addr = acceptor.holdings()[0].local_addr.clone().into_std().unwrap();
+        let server = Server::new(acceptor);
+        let handle = server.handle();
+        let router = Router::new()
+            .push(Router::with_path("slow").get(slow))
+            .push(Router::with_path("hang").get(hang));
+        let mut server = tokio::spawn(server.serve(router));
+
+        // The count of alive connections falls to 0 before the server is stopped.
+        let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        wait_until(|| handle.alive_connections() == 1).await;
+        drop(stream);
+        wait_until(|| handle.alive_connections() == 0).await;
+
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /slow HTTP/1.1\r\nhost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        wait_until(|| handle.stats().in_flight_requests == 1).await;
+        handle.stop_graceful(None);
+        assert!(tokio::time::timeout(Duration::from_millis(200), &mut server)
+            .await
+            .is_err());
+        let mut buf = [0; 1024];
+        let len = stream.read(&mut buf).await.unwrap();
+        assert!(String::from_utf8_lossy(&buf[..len]).ends_with("Slow"));
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("server should stop")
+            .unwrap();
+
+        // The graceful shutdown can be escalated to forcible one.
+        let acceptor = TcpListener::new("127.0.0.1:0").bind().await;
+        let addr = acceptor.holdings()[0].local_addr.clone().into_std().unwrap();
+        let server = Server::new(acceptor);
+        let handle = server.handle();
+        let server = tokio::spawn(server.serve(Router::with_path("hang").get(hang)));
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /hang HTTP/1.1\r\nhost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        wait_until(|| handle.stats().in_flight_requests == 1).await;
+        handle.stop_graceful(None);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        handle.stop_forcible();
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("server should stop")
+            .unwrap();
+    }
 }
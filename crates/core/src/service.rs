@@ -2,9 +2,11 @@ use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 
+use arc_swap::ArcSwap;
 use headers::HeaderValue;
-use http::header::{ALT_SVC, CONTENT_TYPE};
+use http::header::{ALLOW, ALT_SVC, CONTENT_LENGTH, CONTENT_TYPE};
 use http::uri::Scheme;
+use http::Version;
 use hyper::service::Service as HyperService;
 use hyper::{Method, Request as HyperRequest, Response as HyperResponse};
 
@@ -12,17 +14,85 @@ use crate::catcher::{write_error_default, Catcher};
 use crate::conn::SocketAddr;
 use crate::http::body::{ReqBody, ResBody};
 use crate::http::{Mime, Request, Response, StatusCode};
-use crate::routing::{FlowCtrl, PathState, Router};
+use crate::routing::table::ServedRouter;
+use crate::routing::{CompiledRouter, FlowCtrl, PathState, Router, UrlForError};
+use crate::server::ServerState;
+use crate::writer::negotiated::AllowedMediaTypes;
+use crate::writer::SerializerRegistry;
 use crate::Depot;
 
 /// Service http request.
 pub struct Service {
-    /// The router of this service.
-    pub router: Arc<Router>,
-    /// The catcher of this service.
-    pub catcher: Option<Arc<Catcher>>,
     /// The allowed media types of this service.
     pub allowed_media_types: Arc<Vec<Mime>>,
+    /// Answer `OPTIONS` requests automatically with the methods registered in router.
+    pub auto_options: bool,
+    /// Answer `HEAD` requests with the matched `GET` handler when no `HEAD` handler is registered.
+    pub auto_head: bool,
+    /// The key used to verify signed cookies and decrypt private cookies.
+    #[cfg(feature = "cookie")]
+    pub cookie_key: Option<Arc<cookie::Key>>,
+    /// The serializers used by [`Negotiated`](crate::writer::Negotiated), the default registry is used if it is `None`.
+    pub serializers: Option<Arc<SerializerRegistry>>,
+    routing: Arc<ArcSwap<Routing>>,
+}
+
+/// The router and catcher of a [`Service`], they are shared with it's [`ServiceSwapper`]s and handlers.
+pub(crate) struct Routing {
+    router: Arc<CompiledRouter>,
+    catcher: Option<Arc<Catcher>>,
+}
+
+/// Replaces the router and catcher of a running [`Service`], it is returned by [`Service::swapper`].
+///
+/// New requests are handled by the new router and catcher, while in-flight requests finish on the old ones.
+///
+/// # Example
+///
+/// ```no_run
+/// # use salvo_core::prelude::*;
+/// #[handler]
+/// async fn hello() -> &'static str {
+///     "Hello World"
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let service = Service::new(Router::new().get(hello));
+/// let swapper = service.swapper();
+/// tokio::spawn(async move {
+///     // Load the new routing configuration...
+///     swapper.swap_router(Router::with_path("hello").get(hello));
+/// });
+/// let acceptor = TcpListener::new("127.0.0.1:5800").bind().await;
+/// Server::new(acceptor).serve(service).await;
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ServiceSwapper(Arc<ArcSwap<Routing>>);
+impl ServiceSwapper {
+    /// Replace the router of the service.
+    #[inline]
+    pub fn swap_router<T>(&self, router: T)
+    where
+        T: Into<Arc<Router>>,
+    {
+        let compiled = Arc::new(CompiledRouter::new(router));
+        self.0.rcu(|routing| Routing {
+            router: compiled.clone(),
+            catcher: routing.catcher.clone(),
+        });
+    }
+
+    /// Replace the catcher of the service.
+    #[inline]
+    pub fn swap_catcher(&self, catcher: impl Into<Arc<Catcher>>) {
+        let catcher = Some(catcher.into());
+        self.0.rcu(|routing| Routing {
+            router: routing.router.clone(),
+            catcher: catcher.clone(),
+        });
+    }
 }
 
 impl Service {
@@ -33,16 +103,29 @@ impl Service {
         T: Into<Arc<Router>>,
     {
         Service {
-            router: router.into(),
-            catcher: None,
             allowed_media_types: Arc::new(vec![]),
+            auto_options: false,
+            auto_head: false,
+            #[cfg(feature = "cookie")]
+            cookie_key: None,
+            serializers: None,
+            routing: Arc::new(ArcSwap::from_pointee(Routing {
+                router: Arc::new(CompiledRouter::new(router)),
+                catcher: None,
+            })),
         }
     }
 
-    /// Get router in this `Service`.
+    /// Get router in this `Service`, it is the one set by [`ServiceSwapper::swap_router`] if the router is swapped.
     #[inline]
     pub fn router(&self) -> Arc<Router> {
-        self.router.clone()
+        self.routing.load().router.router().clone()
+    }
+
+    /// Get a [`ServiceSwapper`] to replace the router and catcher after the service is started.
+    #[inline]
+    pub fn swapper(&self) -> ServiceSwapper {
+        ServiceSwapper(self.routing.clone())
     }
 
     /// When the response code is 400-600 and the body is empty, capture and set the error page content.
@@ -68,8 +151,8 @@ impl Service {
     /// }
     /// ```
     #[inline]
-    pub fn catcher(mut self, catcher: impl Into<Arc<Catcher>>) -> Self {
-        self.catcher = Some(catcher.into());
+    pub fn catcher(self, catcher: impl Into<Arc<Catcher>>) -> Self {
+        ServiceSwapper(self.routing.clone()).swap_catcher(catcher);
         self
     }
 
@@ -94,6 +177,125 @@ impl Service {
         self
     }
 
+    #[inline]
+    fn compiled_router(&self) -> Arc<CompiledRouter> {
+        self.routing.load().router.clone()
+    }
+
+    /// Generate url for the router with the given name, see [`CompiledRouter::url_for`].
+    ///
+    /// In handlers, [`Depot::url_for`] can be used to generate url.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// #[handler]
+    /// async fn show_user() {}
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let router = Router::with_path("users/<id:num>").name("user.show").get(show_user);
+    /// let service = Service::new(router);
+    /// assert_eq!(service.url_for("user.show", &[("id", "12")], &[]).unwrap(), "/users/12");
+    /// # }
+    /// ```
+    #[inline]
+    pub fn url_for(
+        &self,
+        name: &str,
+        params: &[(&str, &str)],
+        queries: &[(&str, &str)],
+    ) -> Result<String, UrlForError> {
+        self.compiled_router().url_for(name, params, queries)
+    }
+
+    /// Sets whether `OPTIONS` requests are answered automatically and returns `Self` for write code chained.
+    ///
+    /// When enabled and no handler is registered for `OPTIONS`, a `204 No Content` response is returned
+    /// with an `Allow` header listing the methods registered for the request's path.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let service = Service::new(Router::new()).auto_options(true);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn auto_options(mut self, auto_options: bool) -> Self {
+        self.auto_options = auto_options;
+        self
+    }
+
+    /// Sets whether `HEAD` requests fall back to `GET` handlers and returns `Self` for write code chained.
+    ///
+    /// When enabled and no handler is registered for `HEAD`, the matched `GET` handler is used,
+    /// the response body is stripped and it's `Content-Length` is kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let service = Service::new(Router::new()).auto_head(true);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn auto_head(mut self, auto_head: bool) -> Self {
+        self.auto_head = auto_head;
+        self
+    }
+
+    /// Sets the key used to verify signed cookies and decrypt private cookies and returns `Self` for write code
+    /// chained.
+    ///
+    /// The key is available in [`Request::cookie_key`], and it is used by [`Request::signed_cookie`],
+    /// [`Request::private_cookie`] and `Extractible` fields with `signed` or `private` cookie source format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// use salvo_core::http::cookie::Key;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let service = Service::new(Router::new()).cookie_key(Key::generate());
+    /// # }
+    /// ```
+    #[cfg(feature = "cookie")]
+    #[inline]
+    pub fn cookie_key(mut self, key: impl Into<Arc<cookie::Key>>) -> Self {
+        self.cookie_key = Some(key.into());
+        self
+    }
+
+    /// Sets the serializers used by [`Negotiated`](crate::writer::Negotiated) and returns `Self` for write code
+    /// chained.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// use salvo_core::writer::SerializerRegistry;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let service = Service::new(Router::new()).serializers(SerializerRegistry::default());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn serializers(mut self, serializers: impl Into<Arc<SerializerRegistry>>) -> Self {
+        self.serializers = Some(serializers.into());
+        self
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn hyper_handler(
@@ -107,10 +309,15 @@ impl Service {
             local_addr,
             remote_addr,
             http_scheme,
-            router: self.router.clone(),
-            catcher: self.catcher.clone(),
             allowed_media_types: self.allowed_media_types.clone(),
+            auto_options: self.auto_options,
+            auto_head: self.auto_head,
+            #[cfg(feature = "cookie")]
+            cookie_key: self.cookie_key.clone(),
+            serializers: self.serializers.clone(),
             alt_svc_h3,
+            server_state: None,
+            routing: self.routing.clone(),
         }
     }
     /// Handle new request, this function only used for test.
@@ -140,17 +347,20 @@ pub struct HyperHandler {
     pub(crate) local_addr: SocketAddr,
     pub(crate) remote_addr: SocketAddr,
     pub(crate) http_scheme: Scheme,
-    pub(crate) router: Arc<Router>,
-    pub(crate) catcher: Option<Arc<Catcher>>,
     pub(crate) allowed_media_types: Arc<Vec<Mime>>,
+    pub(crate) auto_options: bool,
+    pub(crate) auto_head: bool,
+    #[cfg(feature = "cookie")]
+    pub(crate) cookie_key: Option<Arc<cookie::Key>>,
+    pub(crate) serializers: Option<Arc<SerializerRegistry>>,
     pub(crate) alt_svc_h3: Option<HeaderValue>,
+    pub(crate) server_state: Option<Arc<ServerState>>,
+    pub(crate) routing: Arc<ArcSwap<Routing>>,
 }
 impl HyperHandler {
     /// Handle [`Request`] and returns [`Response`].
     #[inline]
     pub fn handle(&self, mut req: Request) -> impl Future<Output = Response> {
-        let catcher = self.catcher.clone();
-        let allowed_media_types = self.allowed_media_types.clone();
         req.local_addr = self.local_addr.clone();
         req.remote_addr = self.remote_addr.clone();
         #[cfg(not(feature = "cookie"))]
@@ -163,69 +373,148 @@ impl HyperHandler {
             }
         }
         let mut depot = Depot::new();
+        let handler = self.clone();
+        async move {
+            handler.process(&mut req, &mut depot, &mut res).await;
+            res
+        }
+    }
+
+    /// Process request with given depot and response, it is also used by the mounted service.
+    pub(crate) async fn process(&self, req: &mut Request, depot: &mut Depot, res: &mut Response) {
+        // The router and catcher are loaded once, so the request is handled by them even if they are
+        // swapped before it is finished.
+        let routing = self.routing.load_full();
+        let router = &routing.router;
+        let service_catcher = &routing.catcher;
+        if router.has_names() {
+            depot.inject(router.clone());
+        }
+        // The router of the outermost service is kept if this service is mounted.
+        if depot.obtain::<ServedRouter>().is_none() {
+            depot.inject(ServedRouter(router.clone()));
+        }
+        #[cfg(feature = "cookie")]
+        if let Some(key) = &self.cookie_key {
+            req.cookie_key = Some(key.clone());
+        }
+        if let Some(serializers) = &self.serializers {
+            depot.inject(serializers.clone());
+        }
+        if !self.allowed_media_types.is_empty() {
+            depot.inject(AllowedMediaTypes(self.allowed_media_types.clone()));
+        }
         let mut path_state = PathState::new(req.uri().path());
-        let router = self.router.clone();
+        let auto_options = self.auto_options;
+        let auto_head = self.auto_head;
 
-        async move {
-            if let Some(dm) = router.detect(&mut req, &mut path_state) {
-                req.params = path_state.params;
-                let mut ctrl = FlowCtrl::new([&dm.hoops[..], &[dm.handler]].concat());
-                ctrl.call_next(&mut req, &mut depot, &mut res).await;
-                if res.status_code.is_none() {
-                    res.status_code = Some(StatusCode::OK);
+        let mut matched = router.detect(req, &mut path_state);
+        let mut head_fallback = false;
+        if matched.is_none() && auto_head && req.method() == Method::HEAD {
+            *req.method_mut() = Method::GET;
+            let mut get_path_state = PathState::new(req.uri().path());
+            matched = router.detect(req, &mut get_path_state);
+            *req.method_mut() = Method::HEAD;
+            if matched.is_some() {
+                head_fallback = true;
+                path_state = get_path_state;
+            }
+        }
+        let mut catcher = path_state.catcher.take().map(|(_, catcher)| catcher);
+        if let Some(dm) = matched {
+            catcher = dm.catcher;
+            // Params of the parent service are kept if this service is mounted.
+            req.params.extend(path_state.params);
+            let mut ctrl = FlowCtrl::new([&dm.hoops[..], &[dm.handler]].concat());
+            ctrl.call_next(req, depot, res).await;
+            if res.status_code.is_none() {
+                res.status_code = Some(StatusCode::OK);
+            }
+        } else if path_state.once_ended {
+            let mut allowed_methods = router.allowed_methods(req);
+            if !allowed_methods.is_empty() {
+                if auto_head && allowed_methods.contains(&Method::GET) && !allowed_methods.contains(&Method::HEAD) {
+                    allowed_methods.push(Method::HEAD);
                 }
-            } else {
+                if auto_options && !allowed_methods.contains(&Method::OPTIONS) {
+                    allowed_methods.push(Method::OPTIONS);
+                }
+            }
+            if allowed_methods.is_empty() {
                 res.status_code(StatusCode::NOT_FOUND);
+            } else {
+                let allow = allowed_methods
+                    .iter()
+                    .map(|m| m.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if let Ok(allow) = HeaderValue::from_str(&allow) {
+                    res.headers_mut().insert(ALLOW, allow);
+                }
+                if auto_options && req.method() == Method::OPTIONS {
+                    res.status_code(StatusCode::NO_CONTENT);
+                } else {
+                    res.status_code(StatusCode::METHOD_NOT_ALLOWED);
+                }
             }
+        } else {
+            res.status_code(StatusCode::NOT_FOUND);
+        }
 
-            let status = res.status_code.unwrap();
-            let has_error = status.is_client_error() || status.is_server_error();
-            if let Some(value) = res.headers().get(CONTENT_TYPE) {
-                let mut is_allowed = false;
-                if let Ok(value) = value.to_str() {
-                    if allowed_media_types.is_empty() {
-                        is_allowed = true;
-                    } else {
-                        let ctype: Result<Mime, _> = value.parse();
-                        if let Ok(ctype) = ctype {
-                            for mime in &*allowed_media_types {
-                                if mime.type_() == ctype.type_() && mime.subtype() == ctype.subtype() {
-                                    is_allowed = true;
-                                    break;
-                                }
+        let status = res.status_code.unwrap();
+        let has_error = status.is_client_error() || status.is_server_error();
+        if let Some(value) = res.headers().get(CONTENT_TYPE) {
+            let mut is_allowed = false;
+            if let Ok(value) = value.to_str() {
+                if self.allowed_media_types.is_empty() {
+                    is_allowed = true;
+                } else {
+                    let ctype: Result<Mime, _> = value.parse();
+                    if let Ok(ctype) = ctype {
+                        for mime in &*self.allowed_media_types {
+                            if mime.type_() == ctype.type_() && mime.subtype() == ctype.subtype() {
+                                is_allowed = true;
+                                break;
                             }
                         }
                     }
                 }
-                if !is_allowed {
-                    res.status_code(StatusCode::UNSUPPORTED_MEDIA_TYPE);
-                }
-            } else if res.body.is_none()
-                && !has_error
-                && res.status_code != Some(StatusCode::NO_CONTENT)
-                && [Method::GET, Method::POST, Method::PATCH, Method::PUT].contains(req.method())
-            {
-                // check for avoid warning when errors (404 etc.)
-                tracing::warn!(
-                    uri = ?req.uri(),
-                    method = req.method().as_str(),
-                    "http response content type header not set"
-                );
             }
-            if (res.body.is_none() || res.body.is_error()) && has_error {
-                if let Some(catcher) = catcher {
-                    catcher.catch(&mut req, &mut depot, &mut res).await;
-                } else {
-                    write_error_default(&req, &mut res, None);
-                }
+            if !is_allowed {
+                res.status_code(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+            }
+        } else if res.body.is_none()
+            && !has_error
+            && res.status_code != Some(StatusCode::NO_CONTENT)
+            && [Method::GET, Method::POST, Method::PATCH, Method::PUT].contains(req.method())
+        {
+            // check for avoid warning when errors (404 etc.)
+            tracing::warn!(
+                uri = ?req.uri(),
+                method = req.method().as_str(),
+                "http response content type header not set"
+            );
+        }
+        if (res.body.is_none() || res.body.is_error()) && has_error {
+            if let Some(catcher) = catcher.as_ref().or(service_catcher.as_ref()) {
+                catcher.catch(req, depot, res).await;
+            } else {
+                write_error_default(req, res, None);
             }
-            #[cfg(debug_assertions)]
-            if let hyper::Method::HEAD = *req.method() {
-                if !res.body.is_none() {
-                    tracing::warn!("request with head method should not have body: https://developer.mozilla.org/en-US/docs/Web/HTTP/Methods/HEAD");
+        }
+        if head_fallback {
+            if !res.headers().contains_key(CONTENT_LENGTH) {
+                if let Some(size) = res.body.size() {
+                    res.headers_mut().insert(CONTENT_LENGTH, size.into());
                 }
             }
-            res
+            res.body = ResBody::None;
+        }
+        #[cfg(debug_assertions)]
+        if let hyper::Method::HEAD = *req.method() {
+            if !res.body.is_none() {
+                tracing::warn!("request with head method should not have body: https://developer.mozilla.org/en-US/docs/Web/HTTP/Methods/HEAD");
+            }
         }
     }
 }
@@ -262,9 +551,31 @@ where
                 }
             }
         }
+        let in_flight = self
+            .server_state
+            .as_ref()
+            .map(|state| state.start_request(req.version()));
+        let version = req.version();
         let request = Request::from_hyper(req, scheme);
         let response = self.handle(request);
-        Box::pin(async move { Ok(response.await.into_hyper()) })
+        Box::pin(async move {
+            let mut response = response.await;
+            drop(in_flight);
+            // The HTTP/1 connection of hyper does not write chunked trailers, make it explicit instead of
+            // advertising trailers that never arrive.
+            if version < Version::HTTP_2 {
+                if !response.trailers.is_empty() {
+                    tracing::warn!(
+                        ?version,
+                        "response trailers are only sent over HTTP/2 and HTTP/3, discarded"
+                    );
+                    response.trailers.clear();
+                }
+                // Trailers may also be yielded by the frames of body.
+                response.body = std::mem::replace(&mut response.body, ResBody::None).discard_trailers();
+            }
+            Ok(response.into_hyper())
+        })
     }
 }
 
@@ -330,4 +641,231 @@ mod tests {
         let content = access(&service, "3").await;
         assert_eq!(content, "before1before2before3");
     }
+
+    #[tokio::test]
+    async fn test_method_not_allowed() {
+        #[handler]
+        async fn hello() -> &'static str {
+            "hello"
+        }
+        let router = Router::new()
+            .push(Router::with_path("users").get(hello).post(hello))
+            .push(Router::with_path("users/<id>").get(hello).delete(hello));
+        let service = Service::new(router);
+
+        let res = TestClient::put("http://127.0.0.1:5801/users").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::METHOD_NOT_ALLOWED));
+        assert_eq!(res.headers().get("allow").unwrap(), "GET, POST");
+
+        let res = TestClient::post("http://127.0.0.1:5801/users/12").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::METHOD_NOT_ALLOWED));
+        assert_eq!(res.headers().get("allow").unwrap(), "GET, DELETE");
+
+        let res = TestClient::put("http://127.0.0.1:5801/articles").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::NOT_FOUND));
+        assert!(res.headers().get("allow").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_auto_options_and_head() {
+        #[handler]
+        async fn hello() -> &'static str {
+            "hello"
+        }
+        let router = Router::with_path("users").get(hello).post(hello);
+        let service = Service::new(router).auto_options(true).auto_head(true);
+
+        let res = TestClient::options("http://127.0.0.1:5801/users").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::NO_CONTENT));
+        assert_eq!(res.headers().get("allow").unwrap(), "GET, POST, HEAD, OPTIONS");
+
+        let mut res = TestClient::head("http://127.0.0.1:5801/users").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(res.headers().get("content-length").unwrap(), "5");
+        assert!(res.take_string().await.unwrap().is_empty());
+
+        let res = TestClient::put("http://127.0.0.1:5801/users").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::METHOD_NOT_ALLOWED));
+        assert_eq!(res.headers().get("allow").unwrap(), "GET, POST, HEAD, OPTIONS");
+
+        let service = Service::new(Router::with_path("users").get(hello));
+        let res = TestClient::head("http://127.0.0.1:5801/users").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::METHOD_NOT_ALLOWED));
+    }
+
+    #[tokio::test]
+    async fn test_router_catcher() {
+        use crate::catcher::Catcher;
+
+        #[handler]
+        async fn fail(res: &mut Response) {
+            res.status_code(StatusCode::BAD_REQUEST);
+        }
+        #[handler]
+        async fn api_error(res: &mut Response, ctrl: &mut FlowCtrl) {
+            res.render(Text::Json(format!(
+                r#"{{"status":{}}}"#,
+                res.status_code.unwrap().as_u16()
+            )));
+            ctrl.skip_rest();
+        }
+        #[handler]
+        async fn admin_error(res: &mut Response, ctrl: &mut FlowCtrl) {
+            res.render(Text::Html("<h1>admin error</h1>"));
+            ctrl.skip_rest();
+        }
+        #[handler]
+        async fn service_error(res: &mut Response, ctrl: &mut FlowCtrl) {
+            res.render("service error");
+            ctrl.skip_rest();
+        }
+        let router = Router::new()
+            .push(
+                Router::with_path("api")
+                    .catcher(Catcher::default().hoop(api_error))
+                    .push(Router::with_path("fail").get(fail)),
+            )
+            .push(
+                Router::with_path("admin")
+                    .catcher(Catcher::default().hoop(admin_error))
+                    .push(Router::with_path("fail").get(fail)),
+            )
+            .push(Router::with_path("fail").get(fail));
+        let service = Service::new(router).catcher(Catcher::default().hoop(service_error));
+
+        async fn access(service: &Service, url: &str) -> String {
+            TestClient::get(url).send(service).await.take_string().await.unwrap()
+        }
+        assert_eq!(
+            access(&service, "http://127.0.0.1:5801/api/fail").await,
+            r#"{"status":400}"#
+        );
+        assert_eq!(
+            access(&service, "http://127.0.0.1:5801/api/unknown").await,
+            r#"{"status":404}"#
+        );
+        assert_eq!(
+            access(&service, "http://127.0.0.1:5801/admin/fail").await,
+            "<h1>admin error</h1>"
+        );
+        assert_eq!(
+            access(&service, "http://127.0.0.1:5801/admin").await,
+            "<h1>admin error</h1>"
+        );
+        assert_eq!(access(&service, "http://127.0.0.1:5801/fail").await, "service error");
+        assert_eq!(access(&service, "http://127.0.0.1:5801/unknown").await, "service error");
+    }
+
+    #[tokio::test]
+    async fn test_swap_router_and_catcher() {
+        use crate::catcher::Catcher;
+        use crate::service::ServiceSwapper;
+
+        #[handler]
+        async fn hello() -> &'static str {
+            "hello"
+        }
+        #[handler]
+        async fn old_error(res: &mut Response, ctrl: &mut FlowCtrl) {
+            res.render("old error");
+            ctrl.skip_rest();
+        }
+        #[handler]
+        async fn new_error(res: &mut Response, ctrl: &mut FlowCtrl) {
+            res.render("new error");
+            ctrl.skip_rest();
+        }
+        struct SwapAndFail(ServiceSwapper);
+        #[async_trait]
+        impl Handler for SwapAndFail {
+            async fn handle(&self, _req: &mut Request, _depot: &mut Depot, res: &mut Response, _ctrl: &mut FlowCtrl) {
+                self.0.swap_router(Router::with_path("new").get(hello));
+                self.0.swap_catcher(Catcher::default().hoop(new_error));
+                res.status_code(StatusCode::BAD_REQUEST);
+            }
+        }
+
+        let service = Service::new(Router::with_path("old").get(hello)).catcher(Catcher::default().hoop(old_error));
+        let swapper = service.swapper();
+        async fn access(service: &Service, url: &str) -> String {
+            TestClient::get(url).send(service).await.take_string().await.unwrap()
+        }
+        assert_eq!(access(&service, "http://127.0.0.1:5801/old").await, "hello");
+
+        swapper.swap_router(Router::with_path("swap").get(SwapAndFail(swapper.clone())));
+        assert_eq!(access(&service, "http://127.0.0.1:5801/old").await, "old error");
+        // The request which swaps is finished with the catcher loaded when it is started.
+        assert_eq!(access(&service, "http://127.0.0.1:5801/swap").await, "old error");
+        assert_eq!(access(&service, "http://127.0.0.1:5801/new").await, "hello");
+        assert_eq!(access(&service, "http://127.0.0.1:5801/swap").await, "new error");
+        assert_eq!(service.router().routes().count(), 1);
+
+        // The catcher set on the service replaces the swapped one and keeps the swapped router.
+        let service = service.catcher(Catcher::default().hoop(old_error));
+        assert_eq!(access(&service, "http://127.0.0.1:5801/swap").await, "old error");
+        assert_eq!(access(&service, "http://127.0.0.1:5801/new").await, "hello");
+    }
+
+    #[tokio::test]
+    async fn test_trailers_over_connection() {
+        use bytes::Bytes;
+        use http_body_util::{BodyExt, Empty};
+        use hyper::server::conn::{http1, http2};
+
+        use crate::runtimes::TokioExecutor;
+
+        #[handler]
+        async fn grpc(res: &mut Response) {
+            res.render("hello");
+            res.trailers_mut().insert("grpc-status", "0".parse().unwrap());
+        }
+        #[handler]
+        async fn grpc_frames(res: &mut Response) {
+            let mut trailers = http::HeaderMap::new();
+            trailers.insert("grpc-status", "0".parse().unwrap());
+            let frames = vec![
+                Ok::<_, std::io::Error>(hyper::body::Frame::data(Bytes::from("hello"))),
+                Ok(hyper::body::Frame::trailers(trailers)),
+            ];
+            res.frame_streaming(futures_util::stream::iter(frames)).unwrap();
+        }
+        let service = Service::new(
+            Router::new()
+                .get(grpc)
+                .push(Router::with_path("frames").get(grpc_frames)),
+        );
+        let addr: std::net::SocketAddr = "127.0.0.1:5801".parse().unwrap();
+        let handler = service.hyper_handler(addr.into(), addr.into(), http::uri::Scheme::HTTP, None);
+        let request = |uri: &str| hyper::Request::builder().uri(uri).body(Empty::<Bytes>::new()).unwrap();
+
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        tokio::spawn(http2::Builder::new(TokioExecutor).serve_connection(server_io, handler.clone()));
+        let (mut sender, conn) = hyper::client::conn::http2::handshake(TokioExecutor, client_io)
+            .await
+            .unwrap();
+        tokio::spawn(conn);
+        for uri in ["http://127.0.0.1:5801/", "http://127.0.0.1:5801/frames"] {
+            let collected = sender
+                .send_request(request(uri))
+                .await
+                .unwrap()
+                .collect()
+                .await
+                .unwrap();
+            assert_eq!(collected.trailers().unwrap().get("grpc-status").unwrap(), "0");
+            assert_eq!(collected.to_bytes(), "hello");
+        }
+
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        tokio::spawn(http1::Builder::new().serve_connection(server_io, handler));
+        let (mut sender, conn) = hyper::client::conn::http1::handshake(client_io).await.unwrap();
+        tokio::spawn(conn);
+        for uri in ["http://127.0.0.1:5801/", "http://127.0.0.1:5801/frames"] {
+            let res = sender.send_request(request(uri)).await.unwrap();
+            assert!(!res.headers().contains_key(http::header::TRAILER));
+            let collected = res.collect().await.unwrap();
+            assert!(collected.trailers().is_none());
+            assert_eq!(collected.to_bytes(), "hello");
+        }
+    }
 }
@@ -149,6 +149,7 @@ impl ResponseExt for Response {
                 }
                 bytes.freeze()
             }
+            body @ ResBody::Frames(_) => BodyExt::collect(body).await?.to_bytes(),
             ResBody::Error(e) => {
                 if let Some(content_type) = content_type {
                     status_error_bytes(&e, content_type, None).1
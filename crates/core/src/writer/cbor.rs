@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+use serde::Serialize;
+
+use super::Piece;
+use crate::http::header::{HeaderValue, CONTENT_TYPE};
+use crate::http::{Response, StatusError};
+
+/// Write serializable content to response as CBOR content. It will set `content-type` to `application/cbor`.
+pub struct Cbor<T>(pub T);
+#[async_trait]
+impl<T> Piece for Cbor<T>
+where
+    T: Serialize + Send,
+{
+    #[inline]
+    fn render(self, res: &mut Response) {
+        let mut bytes = vec![];
+        match ciborium::ser::into_writer(&self.0, &mut bytes) {
+            Ok(()) => {
+                res.headers_mut().insert(
+                    CONTENT_TYPE,
+                    HeaderValue::from_static("application/cbor"),
+                );
+                res.write_body(bytes).ok();
+            }
+            Err(e) => {
+                tracing::error!(error = ?e, "CborContent write error");
+                res.render(StatusError::internal_server_error());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[tokio::test]
+    async fn test_write_cbor_content() {
+        #[derive(Serialize, Debug)]
+        struct User {
+            name: String,
+        }
+        #[handler]
+        async fn test() -> Cbor<User> {
+            Cbor(User { name: "jobs".into() })
+        }
+
+        let router = Router::new().push(Router::with_path("test").get(test));
+        let mut res = TestClient::get("http://127.0.0.1:5800/test").send(router).await;
+        assert_eq!(res.headers().get("content-type").unwrap(), "application/cbor");
+        let body = res.take_bytes(None).await.unwrap();
+        let value: std::collections::HashMap<String, String> = ciborium::de::from_reader(&body[..]).unwrap();
+        assert_eq!(value["name"], "jobs");
+    }
+}
@@ -1,13 +1,32 @@
 //! Writer trait and it's implements.
 
 mod json;
+pub(crate) mod negotiated;
 mod redirect;
 mod text;
 
+pub use erased_serde;
 pub use json::Json;
+pub use negotiated::{Negotiated, SerializeFn, SerializerRegistry};
 pub use redirect::Redirect;
 pub use text::Text;
 
+cfg_feature! {
+    #![feature = "msgpack"]
+    mod msgpack;
+    pub use msgpack::MsgPack;
+}
+cfg_feature! {
+    #![feature = "cbor"]
+    mod cbor;
+    pub use cbor::Cbor;
+}
+cfg_feature! {
+    #![feature = "xml"]
+    mod xml;
+    pub use xml::Xml;
+}
+
 use crate::http::header::{HeaderValue, CONTENT_TYPE};
 use crate::{async_trait, Depot, Request, Response};
 
@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+use serde::Serialize;
+
+use super::Piece;
+use crate::http::header::{HeaderValue, CONTENT_TYPE};
+use crate::http::{Response, StatusError};
+
+/// Write serializable content to response as MessagePack content. It will set `content-type` to `application/msgpack`.
+pub struct MsgPack<T>(pub T);
+#[async_trait]
+impl<T> Piece for MsgPack<T>
+where
+    T: Serialize + Send,
+{
+    #[inline]
+    fn render(self, res: &mut Response) {
+        match rmp_serde::to_vec_named(&self.0) {
+            Ok(bytes) => {
+                res.headers_mut().insert(
+                    CONTENT_TYPE,
+                    HeaderValue::from_static("application/msgpack"),
+                );
+                res.write_body(bytes).ok();
+            }
+            Err(e) => {
+                tracing::error!(error = ?e, "MsgPackContent write error");
+                res.render(StatusError::internal_server_error());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[tokio::test]
+    async fn test_write_msgpack_content() {
+        #[derive(Serialize, Debug)]
+        struct User {
+            name: String,
+        }
+        #[handler]
+        async fn test() -> MsgPack<User> {
+            MsgPack(User { name: "jobs".into() })
+        }
+
+        let router = Router::new().push(Router::with_path("test").get(test));
+        let mut res = TestClient::get("http://127.0.0.1:5800/test").send(router).await;
+        assert_eq!(res.headers().get("content-type").unwrap(), "application/msgpack");
+        let body = res.take_bytes(None).await.unwrap();
+        let value: std::collections::HashMap<String, String> = rmp_serde::from_slice(&body).unwrap();
+        assert_eq!(value["name"], "jobs");
+    }
+}
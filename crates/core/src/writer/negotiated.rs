@@ -0,0 +1,406 @@
+use std::fmt::{self, Formatter};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use super::Writer;
+use crate::http::header::{HeaderValue, ACCEPT, CONTENT_TYPE, VARY};
+use crate::http::{Mime, Request, Response, StatusError};
+use crate::{BoxedError, Depot};
+
+/// Function which serializes data to bytes of a format.
+pub type SerializeFn = dyn Fn(&dyn erased_serde::Serialize) -> Result<Vec<u8>, BoxedError> + Send + Sync;
+
+static DEFAULT_REGISTRY: Lazy<SerializerRegistry> = Lazy::new(SerializerRegistry::default);
+
+/// Media types set by [`Service::allowed_media_types`](crate::Service::allowed_media_types), it is injected into
+/// depot for [`Negotiated`] when it is not empty.
+pub(crate) struct AllowedMediaTypes(pub(crate) Arc<Vec<Mime>>);
+
+/// Registry of serializers used by [`Negotiated`], it is set by [`Service::serializers`](crate::Service::serializers).
+///
+/// The order of registration is the order of preference, it is used when the `Accept` header is missing or
+/// several serializers are acceptable with the same quality.
+///
+/// The default registry contains JSON, MessagePack (`msgpack` feature), CBOR (`cbor` feature), YAML (`yaml` feature)
+/// and form-urlencoded serializers.
+#[derive(Clone)]
+pub struct SerializerRegistry {
+    serializers: Vec<(Mime, HeaderValue, Arc<SerializeFn>)>,
+}
+impl fmt::Debug for SerializerRegistry {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("SerializerRegistry")
+            .field(
+                "media_types",
+                &self.serializers.iter().map(|(mime, _, _)| mime).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+impl Default for SerializerRegistry {
+    fn default() -> Self {
+        let registry = Self::new().register(
+            "application/json; charset=utf-8".parse().unwrap(),
+            |value: &dyn erased_serde::Serialize| Ok(serde_json::to_vec(value)?),
+        );
+        #[cfg(feature = "msgpack")]
+        let registry = registry.register(
+            "application/msgpack".parse().unwrap(),
+            |value: &dyn erased_serde::Serialize| Ok(rmp_serde::to_vec_named(value)?),
+        );
+        #[cfg(feature = "cbor")]
+        let registry = registry.register(
+            "application/cbor".parse().unwrap(),
+            |value: &dyn erased_serde::Serialize| {
+                let mut bytes = vec![];
+                ciborium::ser::into_writer(&value, &mut bytes)?;
+                Ok(bytes)
+            },
+        );
+        #[cfg(feature = "yaml")]
+        let registry = registry.register(
+            "application/yaml; charset=utf-8".parse().unwrap(),
+            |value: &dyn erased_serde::Serialize| Ok(serde_yaml::to_string(value)?.into_bytes()),
+        );
+        registry.register(
+            mime::APPLICATION_WWW_FORM_URLENCODED,
+            |value: &dyn erased_serde::Serialize| Ok(serde_urlencoded::to_string(value)?.into_bytes()),
+        )
+    }
+}
+impl SerializerRegistry {
+    /// Create an empty registry.
+    #[inline]
+    pub fn new() -> Self {
+        Self { serializers: vec![] }
+    }
+
+    /// Register a serializer for the media type and returns `Self` for write code chained.
+    ///
+    /// The media type is used as response's `Content-Type`, serializer registered before for the same media type
+    /// is replaced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// use salvo_core::writer::{erased_serde, SerializerRegistry};
+    ///
+    /// let registry = SerializerRegistry::default().register(
+    ///     "application/vnd.api+json".parse().unwrap(),
+    ///     |value: &dyn erased_serde::Serialize| Ok(serde_json::to_vec(value)?),
+    /// );
+    /// let service = Service::new(Router::new()).serializers(registry);
+    /// ```
+    pub fn register<F>(mut self, media_type: Mime, serializer: F) -> Self
+    where
+        F: Fn(&dyn erased_serde::Serialize) -> Result<Vec<u8>, BoxedError> + Send + Sync + 'static,
+    {
+        let content_type = HeaderValue::from_str(media_type.as_ref()).expect("media type must be valid header value");
+        self.serializers
+            .retain(|(mime, _, _)| mime.essence_str() != media_type.essence_str());
+        self.serializers.push((media_type, content_type, Arc::new(serializer)));
+        self
+    }
+
+    /// Get media types of all the serializers in the order of preference.
+    #[inline]
+    pub fn media_types(&self) -> impl Iterator<Item = &Mime> {
+        self.serializers.iter().map(|(mime, _, _)| mime)
+    }
+
+    /// Find the acceptable serializers for the `Accept` header value, the most preferred one first.
+    ///
+    /// The quality of a serializer is the one of the most specific range matching it, so `q=0` excludes a media
+    /// type even if a wildcard range matches it.
+    fn negotiate(&self, accept: Option<&str>) -> Vec<&(Mime, HeaderValue, Arc<SerializeFn>)> {
+        let accept = match accept {
+            Some(accept) if !accept.trim().is_empty() => accept,
+            _ => return self.serializers.iter().collect(),
+        };
+        let ranges = accept
+            .split(',')
+            .filter_map(|range| range.trim().parse::<Mime>().ok())
+            .map(|range| {
+                let quality = range
+                    .get_param("q")
+                    .and_then(|q| q.as_str().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (range, quality)
+            })
+            .collect::<Vec<_>>();
+        let mut candidates = self
+            .serializers
+            .iter()
+            .filter_map(|serializer| {
+                let mime = &serializer.0;
+                ranges
+                    .iter()
+                    .filter(|(range, _)| {
+                        (range.type_() == mime::STAR || range.type_() == mime.type_())
+                            && (range.subtype() == mime::STAR || range.subtype() == mime.subtype())
+                    })
+                    .map(|(range, quality)| (specificity(range), *quality))
+                    .max_by(|(sa, qa), (sb, qb)| {
+                        sa.cmp(sb)
+                            .then_with(|| qa.partial_cmp(qb).unwrap_or(std::cmp::Ordering::Equal))
+                    })
+                    .map(|(specificity, quality)| (serializer, specificity, quality))
+            })
+            .filter(|(_, _, quality)| *quality > 0.0)
+            .collect::<Vec<_>>();
+        // Higher quality first, and matched by more specific range first for the same quality, the sort is stable
+        // so the order of registration is kept for the rest.
+        candidates.sort_by(|(_, sa, qa), (_, sb, qb)| {
+            qb.partial_cmp(qa)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| sb.cmp(sa))
+        });
+        candidates.into_iter().map(|(serializer, _, _)| serializer).collect()
+    }
+}
+
+fn specificity(range: &Mime) -> u8 {
+    match (range.type_() == mime::STAR, range.subtype() == mime::STAR) {
+        (true, _) => 0,
+        (false, true) => 1,
+        (false, false) => 2,
+    }
+}
+
+/// Write serializable content to response in the format negotiated from request's `Accept` header.
+///
+/// The serializers in [`SerializerRegistry`] of the service are used, `406 Not Acceptable` is returned if none of
+/// them is acceptable. If a serializer fails, e.g. form-urlencoded can not serialize nested structs, the next
+/// acceptable one is tried. `Vary: Accept` is always added to the response.
+///
+/// # Example
+///
+/// ```
+/// # use salvo_core::prelude::*;
+/// # use serde::Serialize;
+/// #[derive(Serialize)]
+/// struct User {
+///     name: String,
+/// }
+/// #[handler]
+/// async fn show_user() -> Negotiated<User> {
+///     Negotiated(User { name: "jobs".into() })
+/// }
+/// ```
+pub struct Negotiated<T>(pub T);
+#[async_trait]
+impl<T> Writer for Negotiated<T>
+where
+    T: Serialize + Send,
+{
+    async fn write(mut self, req: &mut Request, depot: &mut Depot, res: &mut Response) {
+        res.headers_mut().append(VARY, HeaderValue::from_static("accept"));
+        let registry = depot
+            .obtain::<Arc<SerializerRegistry>>()
+            .map(|registry| &**registry)
+            .unwrap_or(&DEFAULT_REGISTRY);
+        let accept = req.headers().get(ACCEPT).and_then(|accept| accept.to_str().ok());
+        let mut candidates = registry.negotiate(accept);
+        if let Some(allowed) = depot.obtain::<AllowedMediaTypes>() {
+            candidates.retain(|(mime, _, _)| {
+                allowed
+                    .0
+                    .iter()
+                    .any(|allowed| allowed.type_() == mime.type_() && allowed.subtype() == mime.subtype())
+            });
+        }
+        if candidates.is_empty() {
+            res.render(StatusError::not_acceptable());
+            return;
+        }
+        for (mime, content_type, serializer) in candidates {
+            match serializer(&self.0) {
+                Ok(bytes) => {
+                    res.headers_mut().insert(CONTENT_TYPE, content_type.clone());
+                    res.write_body(bytes).ok();
+                    return;
+                }
+                Err(e) => {
+                    tracing::error!(error = ?e, media_type = %mime, "Negotiated write error");
+                }
+            }
+        }
+        res.render(StatusError::internal_server_error());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[derive(Serialize, Deserialize, Eq, PartialEq, Debug)]
+    struct User {
+        name: String,
+    }
+    #[handler]
+    async fn show_user() -> Negotiated<User> {
+        Negotiated(User { name: "jobs".into() })
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let registry = SerializerRegistry::new()
+            .register(mime::APPLICATION_JSON, |_: &dyn erased_serde::Serialize| Ok(vec![]))
+            .register(mime::TEXT_PLAIN, |_: &dyn erased_serde::Serialize| Ok(vec![]))
+            .register(mime::TEXT_CSV, |_: &dyn erased_serde::Serialize| Ok(vec![]));
+        let negotiate = |accept| {
+            registry
+                .negotiate(accept)
+                .first()
+                .map(|(mime, _, _)| mime.essence_str())
+        };
+        assert_eq!(negotiate(None), Some("application/json"));
+        assert_eq!(negotiate(Some("*/*")), Some("application/json"));
+        assert_eq!(negotiate(Some("text/*")), Some("text/plain"));
+        assert_eq!(negotiate(Some("text/*, text/csv")), Some("text/csv"));
+        assert_eq!(
+            negotiate(Some("application/json;q=0.5, text/plain;q=0.8")),
+            Some("text/plain")
+        );
+        assert_eq!(negotiate(Some("*/*;q=0.1, text/csv;q=0")), Some("application/json"));
+        assert_eq!(negotiate(Some("image/png")), None);
+
+        let registry = SerializerRegistry::new()
+            .register(mime::TEXT_CSV, |_: &dyn erased_serde::Serialize| Ok(vec![]))
+            .register(mime::APPLICATION_JSON, |_: &dyn erased_serde::Serialize| Ok(vec![]));
+        let negotiate = |accept| {
+            registry
+                .negotiate(accept)
+                .iter()
+                .map(|(mime, _, _)| mime.essence_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(negotiate(Some("*/*, text/csv;q=0")), vec!["application/json"]);
+        assert_eq!(negotiate(Some("text/*;q=0, */*")), vec!["application/json"]);
+        assert!(negotiate(Some("application/json;q=0, text/csv;q=0")).is_empty());
+        assert_eq!(
+            negotiate(Some("text/*;q=0.5, application/json")),
+            vec!["application/json", "text/csv"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_negotiated_content() {
+        let service = Service::new(Router::new().get(show_user));
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/").send(&service).await;
+        assert_eq!(res.headers().get(VARY).unwrap(), "accept");
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            "application/json; charset=utf-8"
+        );
+        assert_eq!(res.take_string().await.unwrap(), r#"{"name":"jobs"}"#);
+
+        let mut res = TestClient::get("http://127.0.0.1:5800/")
+            .add_header(
+                ACCEPT,
+                "text/html, application/x-www-form-urlencoded;q=0.9, */*;q=0.1",
+                true,
+            )
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "name=jobs");
+
+        let res = TestClient::get("http://127.0.0.1:5800/")
+            .add_header(ACCEPT, "text/html", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::NOT_ACCEPTABLE));
+        assert_eq!(res.headers().get(VARY).unwrap(), "accept");
+
+        let service = Service::new(Router::new().get(show_user)).serializers(SerializerRegistry::new().register(
+            "text/plain; charset=utf-8".parse().unwrap(),
+            |value: &dyn erased_serde::Serialize| Ok(serde_json::to_string_pretty(value)?.into_bytes()),
+        ));
+        let mut res = TestClient::get("http://127.0.0.1:5800/")
+            .add_header(ACCEPT, "application/json, text/*;q=0.5", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "text/plain; charset=utf-8");
+        assert_eq!(res.take_string().await.unwrap(), "{\n  \"name\": \"jobs\"\n}");
+    }
+
+    #[tokio::test]
+    async fn test_write_negotiated_allowed_media_types() {
+        let service =
+            Service::new(Router::new().get(show_user)).allowed_media_types(vec![mime::APPLICATION_WWW_FORM_URLENCODED]);
+        let mut res = TestClient::get("http://127.0.0.1:5800/").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            "application/x-www-form-urlencoded"
+        );
+        assert_eq!(res.take_string().await.unwrap(), "name=jobs");
+
+        let res = TestClient::get("http://127.0.0.1:5800/")
+            .add_header(ACCEPT, "application/json", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::NOT_ACCEPTABLE));
+    }
+
+    #[tokio::test]
+    async fn test_write_negotiated_fallback() {
+        #[derive(Serialize)]
+        struct Team {
+            leader: User,
+        }
+        #[handler]
+        async fn show_team() -> Negotiated<Team> {
+            Negotiated(Team {
+                leader: User { name: "jobs".into() },
+            })
+        }
+        let service = Service::new(Router::new().get(show_team));
+        let mut res = TestClient::get("http://127.0.0.1:5800/")
+            .add_header(
+                ACCEPT,
+                "application/x-www-form-urlencoded, application/json;q=0.5",
+                true,
+            )
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            "application/json; charset=utf-8"
+        );
+        assert_eq!(res.take_string().await.unwrap(), r#"{"leader":{"name":"jobs"}}"#);
+
+        let res = TestClient::get("http://127.0.0.1:5800/")
+            .add_header(ACCEPT, "application/x-www-form-urlencoded", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[tokio::test]
+    async fn test_write_negotiated_msgpack() {
+        let service = Service::new(Router::new().get(show_user));
+        let mut res = TestClient::get("http://127.0.0.1:5800/")
+            .add_header(ACCEPT, "application/json;q=0.9, application/msgpack", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "application/msgpack");
+        let body = res.take_bytes(None).await.unwrap();
+        assert_eq!(
+            rmp_serde::from_slice::<User>(&body).unwrap(),
+            User { name: "jobs".into() }
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+use serde::Serialize;
+
+use super::Piece;
+use crate::http::header::{HeaderValue, CONTENT_TYPE};
+use crate::http::{Response, StatusError};
+
+/// Write serializable content to response as xml content. It will set `content-type` to `application/xml; charset=utf-8`.
+pub struct Xml<T>(pub T);
+#[async_trait]
+impl<T> Piece for Xml<T>
+where
+    T: Serialize + Send,
+{
+    #[inline]
+    fn render(self, res: &mut Response) {
+        match quick_xml::se::to_string(&self.0) {
+            Ok(bytes) => {
+                res.headers_mut().insert(
+                    CONTENT_TYPE,
+                    HeaderValue::from_static("application/xml; charset=utf-8"),
+                );
+                res.write_body(bytes).ok();
+            }
+            Err(e) => {
+                tracing::error!(error = ?e, "XmlContent write error");
+                res.render(StatusError::internal_server_error());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[tokio::test]
+    async fn test_write_xml_content() {
+        #[derive(Serialize, Debug)]
+        struct User {
+            name: String,
+        }
+        #[handler]
+        async fn test() -> Xml<User> {
+            Xml(User { name: "jobs".into() })
+        }
+
+        let router = Router::new().push(Router::with_path("test").get(test));
+        let mut res = TestClient::get("http://127.0.0.1:5800/test").send(router).await;
+        assert_eq!(res.take_string().await.unwrap(), "<User><name>jobs</name></User>");
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/xml; charset=utf-8"
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! Decompress the body of a request.
+use std::io::{self, Error as IoError, ErrorKind, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use brotli::DecompressorWriter as BrotliDecoder;
+use bytes::{Bytes, BytesMut};
+use flate2::write::{GzDecoder, ZlibDecoder};
+use zstd::stream::write::Decoder as ZstdDecoder;
+
+use super::{CompressionAlgo, DecompressedTooLarge};
+
+/// Writer which fails once the total written size exceeds the limit, it stops decoders writing more data.
+///
+/// `exceeded` is set when the limit is passed, decoders may wrap the error of the writer.
+pub(super) struct LimitedWriter {
+    buf: BytesMut,
+    written: u64,
+    max_size: u64,
+    exceeded: Arc<AtomicBool>,
+}
+
+impl LimitedWriter {
+    fn new(max_size: u64, exceeded: Arc<AtomicBool>) -> LimitedWriter {
+        LimitedWriter {
+            buf: BytesMut::with_capacity(8192),
+            written: 0,
+            max_size,
+            exceeded,
+        }
+    }
+
+    fn take(&mut self) -> Bytes {
+        self.buf.split().freeze()
+    }
+}
+
+impl io::Write for LimitedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.written += buf.len() as u64;
+        if self.written > self.max_size {
+            self.exceeded.store(true, Ordering::Release);
+            return Err(IoError::new(ErrorKind::InvalidData, DecompressedTooLarge));
+        }
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+pub(super) enum Decoder {
+    Deflate(ZlibDecoder<LimitedWriter>),
+    Gzip(GzDecoder<LimitedWriter>),
+    Brotli(Box<BrotliDecoder<LimitedWriter>>),
+    Zstd(ZstdDecoder<'static, LimitedWriter>),
+}
+
+impl Decoder {
+    pub(super) fn new(algo: CompressionAlgo, max_size: u64, exceeded: Arc<AtomicBool>) -> Result<Self, IoError> {
+        let writer = LimitedWriter::new(max_size, exceeded);
+        Ok(match algo {
+            CompressionAlgo::Deflate => Self::Deflate(ZlibDecoder::new(writer)),
+            CompressionAlgo::Gzip => Self::Gzip(GzDecoder::new(writer)),
+            CompressionAlgo::Brotli => Self::Brotli(Box::new(BrotliDecoder::new(writer, 32 * 1024))),
+            CompressionAlgo::Zstd => Self::Zstd(ZstdDecoder::new(writer)?),
+        })
+    }
+
+    /// Write compressed data and take the decompressed data.
+    pub(super) fn decode(&mut self, data: &[u8]) -> Result<Bytes, IoError> {
+        match *self {
+            Self::Deflate(ref mut decoder) => {
+                decoder.write_all(data)?;
+                decoder.flush()?;
+                Ok(decoder.get_mut().take())
+            }
+            Self::Gzip(ref mut decoder) => {
+                decoder.write_all(data)?;
+                decoder.flush()?;
+                Ok(decoder.get_mut().take())
+            }
+            Self::Brotli(ref mut decoder) => {
+                decoder.write_all(data)?;
+                decoder.flush()?;
+                Ok(decoder.get_mut().take())
+            }
+            Self::Zstd(ref mut decoder) => {
+                decoder.write_all(data)?;
+                decoder.flush()?;
+                Ok(decoder.get_mut().take())
+            }
+        }
+    }
+
+    /// Finish the stream and take the rest decompressed data.
+    pub(super) fn finish(self) -> Result<Bytes, IoError> {
+        match self {
+            Self::Deflate(decoder) => Ok(decoder.finish()?.buf.freeze()),
+            Self::Gzip(decoder) => Ok(decoder.finish()?.buf.freeze()),
+            Self::Brotli(mut decoder) => {
+                decoder.close()?;
+                Ok(decoder.get_mut().take())
+            }
+            Self::Zstd(mut decoder) => {
+                decoder.flush()?;
+                Ok(decoder.into_inner().buf.freeze())
+            }
+        }
+    }
+}
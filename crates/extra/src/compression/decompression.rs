@@ -0,0 +1,407 @@
+//! Decompress the body of a request.
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Formatter};
+use std::io::Error as IoError;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures_util::ready;
+use indexmap::IndexSet;
+use salvo_core::http::body::{Body, ReqBody};
+use salvo_core::http::header::{HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH};
+use salvo_core::http::StatusError;
+use salvo_core::hyper::body::Frame;
+use salvo_core::{async_trait, BoxedError, Depot, FlowCtrl, Handler, Request, Response};
+
+use super::decoder::Decoder;
+use super::CompressionAlgo;
+
+/// Error of reading the decoded request body once it's size exceeds [`Decompression::max_size`].
+///
+/// It is the error of the body, so it is wrapped in the [`std::io::Error`] of the request body, which is in
+/// [`ParseError::Other`](salvo_core::http::ParseError::Other) returned by [`Request::payload`] and the other methods
+/// which read the body.
+#[derive(Debug)]
+pub struct DecompressedTooLarge;
+
+impl Display for DecompressedTooLarge {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "decompressed request body is too large")
+    }
+}
+
+impl StdError for DecompressedTooLarge {}
+
+/// Body which decodes the inner body with the decoders in order.
+struct DecodeBody {
+    body: ReqBody,
+    // `Mutex` only makes the body `Sync`, it is always accessed by `get_mut`.
+    decoders: Mutex<Vec<Decoder>>,
+    exceeded: Arc<AtomicBool>,
+    eof: bool,
+}
+impl DecodeBody {
+    fn new(body: ReqBody, decoders: Vec<Decoder>, exceeded: Arc<AtomicBool>) -> Self {
+        Self {
+            body,
+            decoders: Mutex::new(decoders),
+            exceeded,
+            eof: false,
+        }
+    }
+
+    fn error(&self, e: IoError) -> BoxedError {
+        if self.exceeded.load(Ordering::Acquire) {
+            DecompressedTooLarge.into()
+        } else {
+            e.into()
+        }
+    }
+
+    fn decoders(&mut self) -> &mut Vec<Decoder> {
+        self.decoders.get_mut().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn decode(&mut self, data: Bytes) -> Result<Bytes, IoError> {
+        let mut data = data;
+        for decoder in self.decoders() {
+            data = decoder.decode(&data)?;
+        }
+        Ok(data)
+    }
+
+    fn finish(&mut self) -> Result<Bytes, IoError> {
+        let mut data = Bytes::new();
+        for mut decoder in std::mem::take(self.decoders()) {
+            if !data.is_empty() {
+                data = decoder.decode(&data)?;
+                data = [data, decoder.finish()?].concat().into();
+            } else {
+                data = decoder.finish()?;
+            }
+        }
+        Ok(data)
+    }
+}
+impl Body for DecodeBody {
+    type Data = Bytes;
+    type Error = BoxedError;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, BoxedError>>> {
+        let this = self.get_mut();
+        loop {
+            if this.eof {
+                return Poll::Ready(None);
+            }
+            match ready!(Pin::new(&mut this.body).poll_frame(cx)) {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(data) => {
+                        let data = this.decode(data).map_err(|e| this.error(e))?;
+                        if !data.is_empty() {
+                            return Poll::Ready(Some(Ok(Frame::data(data))));
+                        }
+                    }
+                    Err(frame) => return Poll::Ready(Some(Ok(frame))),
+                },
+                Some(Err(e)) => return Poll::Ready(Some(Err(e.into()))),
+                None => {
+                    this.eof = true;
+                    let data = this.finish().map_err(|e| this.error(e))?;
+                    if !data.is_empty() {
+                        return Poll::Ready(Some(Ok(Frame::data(data))));
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.eof
+    }
+}
+
+/// Decompress the body of a request with `Content-Encoding` header.
+///
+/// `Content-Encoding` and `Content-Length` headers are removed from the request after the body is replaced with
+/// the decoded one. Requests encoded with unsupported algorithms are rejected with `415 Unsupported Media Type`.
+///
+/// Reading the decoded body fails with [`DecompressedTooLarge`] once the decompressed size exceeds
+/// [`max_size`](Self::max_size), so that a small compressed body can not be expanded to an unlimited size,
+/// and `413 Payload Too Large` is rendered after the handlers.
+#[derive(Clone, Debug)]
+pub struct Decompression {
+    /// Decompression algorithms to use.
+    pub algos: IndexSet<CompressionAlgo>,
+    /// Max size of the decompressed body.
+    pub max_size: u64,
+}
+
+impl Default for Decompression {
+    #[inline]
+    fn default() -> Self {
+        let mut algos = IndexSet::new();
+        algos.insert(CompressionAlgo::Zstd);
+        algos.insert(CompressionAlgo::Gzip);
+        algos.insert(CompressionAlgo::Deflate);
+        algos.insert(CompressionAlgo::Brotli);
+        Self {
+            algos,
+            max_size: 10 * 1024 * 1024,
+        }
+    }
+}
+
+impl Decompression {
+    /// Create a new `Decompression`, decompressed body size is limited to 10 MiB by default.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Remove all decompression algorithms.
+    #[inline]
+    pub fn disable_all(mut self) -> Self {
+        self.algos.clear();
+        self
+    }
+
+    /// Enable gzip decompression.
+    #[inline]
+    pub fn enable_gzip(mut self) -> Self {
+        self.algos.insert(CompressionAlgo::Gzip);
+        self
+    }
+    /// Disable gzip decompression.
+    #[inline]
+    pub fn disable_gzip(mut self) -> Self {
+        self.algos.shift_remove(&CompressionAlgo::Gzip);
+        self
+    }
+    /// Enable zstd decompression.
+    #[inline]
+    pub fn enable_zstd(mut self) -> Self {
+        self.algos.insert(CompressionAlgo::Zstd);
+        self
+    }
+    /// Disable zstd decompression.
+    #[inline]
+    pub fn disable_zstd(mut self) -> Self {
+        self.algos.shift_remove(&CompressionAlgo::Zstd);
+        self
+    }
+    /// Enable brotli decompression.
+    #[inline]
+    pub fn enable_brotli(mut self) -> Self {
+        self.algos.insert(CompressionAlgo::Brotli);
+        self
+    }
+    /// Disable brotli decompression.
+    #[inline]
+    pub fn disable_brotli(mut self) -> Self {
+        self.algos.shift_remove(&CompressionAlgo::Brotli);
+        self
+    }
+    /// Enable deflate decompression.
+    #[inline]
+    pub fn enable_deflate(mut self) -> Self {
+        self.algos.insert(CompressionAlgo::Deflate);
+        self
+    }
+    /// Disable deflate decompression.
+    #[inline]
+    pub fn disable_deflate(mut self) -> Self {
+        self.algos.shift_remove(&CompressionAlgo::Deflate);
+        self
+    }
+
+    /// Sets max size of the decompressed body.
+    #[inline]
+    pub fn max_size(mut self, size: u64) -> Self {
+        self.max_size = size;
+        self
+    }
+
+    /// Parse `Content-Encoding` header, returns algorithms in the order of decoding.
+    fn parse_encodings(&self, header: &str) -> Option<Vec<CompressionAlgo>> {
+        let mut algos = vec![];
+        for encoding in header.split(',').map(str::trim).rev() {
+            if encoding.is_empty() || encoding.eq_ignore_ascii_case("identity") {
+                continue;
+            }
+            let algo = encoding.to_ascii_lowercase().parse().ok()?;
+            if !self.algos.contains(&algo) {
+                return None;
+            }
+            algos.push(algo);
+        }
+        Some(algos)
+    }
+
+    fn accept_encoding(&self) -> HeaderValue {
+        let algos = self
+            .algos
+            .iter()
+            .map(|algo| HeaderValue::from(*algo).to_str().unwrap_or_default().to_owned())
+            .collect::<Vec<_>>();
+        HeaderValue::from_str(&algos.join(", ")).unwrap_or_else(|_| HeaderValue::from_static("identity"))
+    }
+}
+
+#[async_trait]
+impl Handler for Decompression {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        let algos = match req.headers().get(CONTENT_ENCODING) {
+            Some(header) => header.to_str().ok().and_then(|header| self.parse_encodings(header)),
+            None => return,
+        };
+        let exceeded = Arc::new(AtomicBool::new(false));
+        let decoders = match algos.map(|algos| {
+            algos
+                .into_iter()
+                .map(|algo| Decoder::new(algo, self.max_size, exceeded.clone()))
+                .collect::<Result<Vec<_>, _>>()
+        }) {
+            Some(Ok(decoders)) => decoders,
+            Some(Err(e)) => {
+                tracing::error!(error = ?e, "create decoder failed");
+                res.render(StatusError::internal_server_error());
+                ctrl.skip_rest();
+                return;
+            }
+            None => {
+                res.headers_mut().insert(ACCEPT_ENCODING, self.accept_encoding());
+                res.render(StatusError::unsupported_media_type().brief("Unsupported content encoding."));
+                ctrl.skip_rest();
+                return;
+            }
+        };
+        req.headers_mut().remove(CONTENT_ENCODING);
+        if decoders.is_empty() {
+            return;
+        }
+        req.headers_mut().remove(CONTENT_LENGTH);
+        let body = req.take_body();
+        req.replace_body(ReqBody::Inner(Box::pin(DecodeBody::new(body, decoders, exceeded.clone()))));
+
+        ctrl.call_next(req, depot, res).await;
+        if exceeded.load(Ordering::Acquire) {
+            res.render(StatusError::payload_too_large().brief("Decompressed request body is too large."));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::http::ParseError;
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::super::encoder::Encoder;
+    use super::super::CompressionLevel;
+    use super::*;
+
+    #[handler]
+    async fn echo(req: &mut Request) -> String {
+        let encoded = req.headers().contains_key(CONTENT_ENCODING);
+        match req.payload().await {
+            Ok(payload) => format!("{encoded}: {}", String::from_utf8_lossy(payload)),
+            Err(e) => format!("{encoded}: {e}"),
+        }
+    }
+
+    fn encode(algo: CompressionAlgo, data: &[u8]) -> Vec<u8> {
+        let mut encoder = Encoder::new(algo, CompressionLevel::Default);
+        encoder.write(data).unwrap();
+        encoder.finish().unwrap().to_vec()
+    }
+
+    async fn post(service: &Service, encoding: &str, body: Vec<u8>) -> Response {
+        TestClient::post("http://127.0.0.1:5801/echo")
+            .add_header(CONTENT_ENCODING, encoding, true)
+            .body(body)
+            .send(service)
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_decompress() {
+        let service = Service::new(Router::with_hoop(Decompression::new()).push(Router::with_path("echo").post(echo)));
+        for (algo, encoding) in [
+            (CompressionAlgo::Gzip, "gzip"),
+            (CompressionAlgo::Deflate, "deflate"),
+            (CompressionAlgo::Brotli, "br"),
+            (CompressionAlgo::Zstd, "zstd"),
+        ] {
+            let mut res = post(&service, encoding, encode(algo, br#"{"name":"salvo"}"#)).await;
+            assert_eq!(res.take_string().await.unwrap(), r#"false: {"name":"salvo"}"#);
+        }
+
+        let body = encode(CompressionAlgo::Brotli, &encode(CompressionAlgo::Gzip, b"hello"));
+        let mut res = post(&service, "identity, gzip, br", body).await;
+        assert_eq!(res.take_string().await.unwrap(), "false: hello");
+
+        let mut res = TestClient::post("http://127.0.0.1:5801/echo")
+            .body("hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "false: hello");
+    }
+
+    #[tokio::test]
+    async fn test_decompress_unsupported() {
+        let router = Router::with_hoop(Decompression::new().disable_all().enable_gzip().enable_zstd())
+            .push(Router::with_path("echo").post(echo));
+        let service = Service::new(router);
+        let res = post(&service, "br", encode(CompressionAlgo::Brotli, b"hello")).await;
+        assert_eq!(res.status_code, Some(StatusCode::UNSUPPORTED_MEDIA_TYPE));
+        assert_eq!(res.headers().get(ACCEPT_ENCODING).unwrap(), "gzip, zstd");
+
+        let res = post(&service, "compress", b"hello".to_vec()).await;
+        assert_eq!(res.status_code, Some(StatusCode::UNSUPPORTED_MEDIA_TYPE));
+    }
+
+    #[tokio::test]
+    async fn test_decompress_max_size() {
+        #[handler]
+        async fn read(req: &mut Request, res: &mut Response) {
+            if let Err(ParseError::Other(e)) = req.payload().await {
+                let too_large = e
+                    .downcast_ref::<IoError>()
+                    .and_then(|e| e.get_ref())
+                    .map(|e| e.is::<DecompressedTooLarge>())
+                    .unwrap_or(false);
+                res.add_header("x-too-large", too_large.to_string(), true).unwrap();
+            }
+        }
+        let router = Router::with_hoop(Decompression::new().max_size(1024))
+            .push(Router::with_path("echo").post(echo))
+            .push(Router::with_path("read").post(read));
+        let service = Service::new(router);
+        let res = TestClient::post("http://127.0.0.1:5801/read")
+            .add_header(CONTENT_ENCODING, "gzip", true)
+            .body(encode(CompressionAlgo::Gzip, &vec![0; 4096]))
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::PAYLOAD_TOO_LARGE));
+        assert_eq!(res.headers().get("x-too-large").unwrap(), "true");
+
+        for (algo, encoding) in [
+            (CompressionAlgo::Gzip, "gzip"),
+            (CompressionAlgo::Deflate, "deflate"),
+            (CompressionAlgo::Brotli, "br"),
+            (CompressionAlgo::Zstd, "zstd"),
+        ] {
+            let body = encode(algo, &vec![0; 1024 * 1024]);
+            assert!(body.len() < 8 * 1024);
+            let res = post(&service, encoding, body).await;
+            assert_eq!(res.status_code, Some(StatusCode::PAYLOAD_TOO_LARGE));
+
+            let mut res = post(&service, encoding, encode(algo, &[b'a'; 1024])).await;
+            assert_eq!(res.take_string().await.unwrap().len(), 1024 + "false: ".len());
+        }
+    }
+}
@@ -1,4 +1,4 @@
-//! Compress the body of a response.
+//! Compress the body of a response and decompress the body of a request.
 use std::str::FromStr;
 
 use indexmap::IndexMap;
@@ -10,10 +10,13 @@ use salvo_core::http::header::{
 use salvo_core::http::{Mime, StatusCode};
 use salvo_core::{async_trait, Depot, FlowCtrl, Handler, Request, Response};
 
+mod decoder;
+mod decompression;
 mod encoder;
 mod stream;
+pub use decompression::{DecompressedTooLarge, Decompression};
 use encoder::Encoder;
-use stream::EncodeStream;
+use stream::{EncodeFrames, EncodeStream};
 
 /// Level of compression data should be compressed with.
 #[non_exhaustive]
@@ -348,6 +351,16 @@ impl Handler for Compression {
                     return;
                 }
             },
+            ResBody::Frames(body) => match self.negotiate(req, res) {
+                Some((algo, level)) => {
+                    res.frame_streaming(EncodeFrames::new(algo, level, body)).ok();
+                    res.headers_mut().append(CONTENT_ENCODING, algo.into());
+                }
+                None => {
+                    res.body(ResBody::Frames(body));
+                    return;
+                }
+            },
             _ => {}
         }
         res.headers_mut().remove(CONTENT_LENGTH);
@@ -408,4 +421,43 @@ mod tests {
         let content = res.take_string().await.unwrap();
         assert_eq!(content, "hello");
     }
+
+    #[tokio::test]
+    async fn test_frames_with_trailers() {
+        use std::io::Read;
+
+        use flate2::read::GzDecoder;
+        use futures_util::stream;
+        use http_body_util::BodyExt;
+        use salvo_core::http::body::Frame;
+        use salvo_core::http::header::{HeaderMap, CONTENT_TYPE};
+
+        #[handler]
+        async fn with_trailers(res: &mut Response) {
+            let mut trailers = HeaderMap::new();
+            trailers.insert("x-checksum", "5d41402a".parse().unwrap());
+            let frames = vec![
+                Ok::<_, std::io::Error>(Frame::data("hello ".into())),
+                Ok(Frame::data("world".into())),
+                Ok(Frame::trailers(trailers)),
+            ];
+            res.add_header(CONTENT_TYPE, "text/plain", true).unwrap();
+            res.frame_streaming(stream::iter(frames)).unwrap();
+        }
+        let comp_handler = Compression::new().min_length(1);
+        let router = Router::with_hoop(comp_handler).push(Router::with_path("frames").get(with_trailers));
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/frames")
+            .add_header(ACCEPT_ENCODING, "gzip", true)
+            .send(router)
+            .await;
+        assert_eq!(res.headers().get(CONTENT_ENCODING).unwrap(), "gzip");
+        let collected = BodyExt::collect(res.take_body()).await.unwrap();
+        assert_eq!(collected.trailers().unwrap().get("x-checksum").unwrap(), "5d41402a");
+        let mut content = String::new();
+        GzDecoder::new(&collected.to_bytes()[..])
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "hello world");
+    }
 }
@@ -10,13 +10,20 @@ use futures_util::ready;
 use futures_util::stream::{BoxStream, Stream};
 use tokio::task::{spawn_blocking, JoinHandle};
 
-use salvo_core::http::body::{Body, HyperBody};
+use salvo_core::http::body::{Body, Frame, HyperBody};
+use salvo_core::http::header::HeaderMap;
 use salvo_core::BoxedError;
 
 use super::{CompressionAlgo, CompressionLevel, Encoder};
 
 const MAX_CHUNK_SIZE_ENCODE_IN_PLACE: usize = 1024;
 
+/// Frames of a body, the trailers are kept aside while the data is encoded.
+pub(super) struct FrameBody {
+    frames: BoxStream<'static, Result<Frame<Bytes>, BoxedError>>,
+    trailers: Option<HeaderMap>,
+}
+
 pub(super) struct EncodeStream<B> {
     encoder: Option<Encoder>,
     body: B,
@@ -50,6 +57,25 @@ impl EncodeStream<HyperBody> {
         }
     }
 }
+impl EncodeStream<FrameBody> {
+    #[inline]
+    fn poll_chunk(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes, IoError>>> {
+        loop {
+            match ready!(Stream::poll_next(Pin::new(&mut self.body.frames), cx)) {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(data) => return Poll::Ready(Some(Ok(data))),
+                    Err(frame) => {
+                        if let Ok(trailers) = frame.into_trailers() {
+                            self.body.trailers.get_or_insert_with(HeaderMap::new).extend(trailers);
+                        }
+                    }
+                },
+                Some(Err(e)) => return Poll::Ready(Some(Err(IoError::new(ErrorKind::Other, e)))),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
 impl EncodeStream<Option<Bytes>> {
     #[inline]
     fn poll_chunk(&mut self, _cx: &mut Context<'_>) -> Poll<Option<Result<Bytes, IoError>>> {
@@ -140,5 +166,34 @@ macro_rules! impl_stream {
 }
 impl_stream!(BoxStream<'static, Result<Bytes, BoxedError>>);
 impl_stream!(HyperBody);
+impl_stream!(FrameBody);
 impl_stream!(Option<Bytes>);
 impl_stream!(VecDeque<Bytes>);
+
+/// Compress the data frames of a body and send the trailers after the compressed data.
+pub(super) struct EncodeFrames {
+    inner: EncodeStream<FrameBody>,
+}
+
+impl EncodeFrames {
+    pub(super) fn new(
+        algo: CompressionAlgo,
+        level: CompressionLevel,
+        frames: BoxStream<'static, Result<Frame<Bytes>, BoxedError>>,
+    ) -> Self {
+        Self {
+            inner: EncodeStream::new(algo, level, FrameBody { frames, trailers: None }),
+        }
+    }
+}
+
+impl Stream for EncodeFrames {
+    type Item = Result<Frame<Bytes>, IoError>;
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        match ready!(Pin::new(&mut this.inner).poll_next(cx)) {
+            Some(chunk) => Poll::Ready(Some(chunk.map(Frame::data))),
+            None => Poll::Ready(this.inner.body.trailers.take().map(|trailers| Ok(Frame::trailers(trailers)))),
+        }
+    }
+}
@@ -1,26 +1,144 @@
 //! size limiter middleware
+use std::io::{Error as IoError, ErrorKind};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
 
+use salvo_core::http::body::{Body, ReqBody, SizeHint};
 use salvo_core::http::StatusError;
-use salvo_core::http::{Body, Request, Response};
-use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+use salvo_core::http::{Request, Response};
+use salvo_core::hyper::body::{Bytes, Frame};
+use salvo_core::{async_trait, BoxedError, Depot, FlowCtrl, Handler};
 
-/// MaxSize
+/// Limit of the request body shared by `MaxSize` handlers and the counting body.
+#[derive(Debug)]
+struct BodyLimit {
+    max_size: AtomicU64,
+    read: AtomicU64,
+    polled: AtomicBool,
+    exceeded: AtomicBool,
+}
+impl BodyLimit {
+    /// Checks the upper bound of the body size hint, such as `Content-Length`, against the limit in effect.
+    fn hint_exceeded(&self, upper: Option<u64>) -> bool {
+        match upper {
+            Some(upper) if upper > self.max_size.load(Ordering::Acquire) => {
+                self.exceeded.store(true, Ordering::Release);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Body which counts the read bytes and fails once the count passes the limit.
+struct LimitedBody {
+    body: ReqBody,
+    limit: Arc<BodyLimit>,
+}
+impl Body for LimitedBody {
+    type Data = Bytes;
+    type Error = BoxedError;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, BoxedError>>> {
+        let this = self.get_mut();
+        if this.limit.exceeded.load(Ordering::Acquire) {
+            return Poll::Ready(Some(Err(too_large())));
+        }
+        if !this.limit.polled.swap(true, Ordering::AcqRel) && this.limit.hint_exceeded(this.body.size_hint().upper()) {
+            return Poll::Ready(Some(Err(too_large())));
+        }
+        match ready!(Pin::new(&mut this.body).poll_frame(cx)) {
+            Some(Ok(frame)) => {
+                if let Some(data) = frame.data_ref() {
+                    let read = this.limit.read.fetch_add(data.len() as u64, Ordering::AcqRel) + data.len() as u64;
+                    if read > this.limit.max_size.load(Ordering::Acquire) {
+                        this.limit.exceeded.store(true, Ordering::Release);
+                        return Poll::Ready(Some(Err(too_large())));
+                    }
+                }
+                Poll::Ready(Some(Ok(frame)))
+            }
+            Some(Err(e)) => Poll::Ready(Some(Err(e.into()))),
+            None => Poll::Ready(None),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.body.size_hint()
+    }
+}
+
+fn too_large() -> BoxedError {
+    IoError::new(ErrorKind::InvalidData, "request body is too large").into()
+}
+
+/// Extension for Depot.
+pub trait MaxSizeDepotExt {
+    /// Override max size of current request's body, returns `false` if no `MaxSize` is executed before.
+    fn set_max_size(&mut self, size: u64) -> bool;
+    /// Get max size of current request's body.
+    fn max_size(&self) -> Option<u64>;
+}
+
+impl MaxSizeDepotExt for Depot {
+    #[inline]
+    fn set_max_size(&mut self, size: u64) -> bool {
+        match self.obtain::<Arc<BodyLimit>>() {
+            Some(limit) => {
+                limit.max_size.store(size, Ordering::Release);
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[inline]
+    fn max_size(&self) -> Option<u64> {
+        self.obtain::<Arc<BodyLimit>>()
+            .map(|limit| limit.max_size.load(Ordering::Acquire))
+    }
+}
+
+/// MaxSize limits the size of request body.
+///
+/// The body is counted while it is read, so chunked requests and requests with false `Content-Length` are
+/// handled too. `413 Payload Too Large` is rendered once the read bytes pass the limit.
+///
+/// A `MaxSize` added after another one overrides the limit for the routes it applies to,
+/// [`MaxSizeDepotExt::set_max_size`] can also be used to change it in handlers. `Content-Length` is
+/// therefore checked against the limit in effect when the body is first read, the request is rejected
+/// before any byte is read if it exceeds the limit. The response is left untouched if the body is never read.
 pub struct MaxSize(pub u64);
 #[async_trait]
 impl Handler for MaxSize {
     #[inline]
     async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
-        let size_hint = req.body().size_hint().upper();
-        if let Some(upper) = size_hint {
-            if upper > self.0 {
-                res.render(StatusError::payload_too_large());
-                ctrl.skip_rest();
-            } else {
-                ctrl.call_next(req, depot, res).await;
-            }
-        } else {
-            res.render(StatusError::bad_request().brief("Request body size is unknown."));
-            ctrl.skip_rest();
+        if depot.set_max_size(self.0) {
+            return;
+        }
+
+        let limit = Arc::new(BodyLimit {
+            max_size: AtomicU64::new(self.0),
+            read: AtomicU64::new(0),
+            polled: AtomicBool::new(false),
+            exceeded: AtomicBool::new(false),
+        });
+        let body = req.take_body();
+        req.replace_body(ReqBody::Inner(Box::pin(LimitedBody {
+            body,
+            limit: limit.clone(),
+        })));
+        depot.inject(limit.clone());
+
+        ctrl.call_next(req, depot, res).await;
+        if limit.exceeded.load(Ordering::Acquire) {
+            res.render(StatusError::payload_too_large());
         }
     }
 }
@@ -32,6 +150,8 @@ pub fn max_size(size: u64) -> MaxSize {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::VecDeque;
+
     use salvo_core::prelude::*;
     use salvo_core::test::{ResponseExt, TestClient};
 
@@ -42,15 +162,101 @@ mod tests {
         "hello"
     }
 
+    #[handler]
+    async fn upload(req: &mut Request) -> Result<String, StatusError> {
+        let payload = req.payload().await.map_err(|_| StatusError::bad_request())?;
+        Ok(format!("{}", payload.len()))
+    }
+
+    #[handler]
+    async fn read_hello(req: &mut Request) -> Result<&'static str, StatusError> {
+        req.payload().await.map_err(|_| StatusError::bad_request())?;
+        Ok("hello")
+    }
+
+    #[handler]
+    async fn shrink_limit(depot: &mut Depot) {
+        assert_eq!(depot.max_size(), Some(1024));
+        assert!(depot.set_max_size(8));
+    }
+
+    /// Body sends chunks with an optional fake exact size hint.
+    struct ChunkedBody {
+        chunks: VecDeque<Bytes>,
+        size_hint: Option<u64>,
+    }
+    impl ChunkedBody {
+        fn body(chunks: usize, size_hint: Option<u64>) -> ReqBody {
+            let chunks = (0..chunks).map(|_| Bytes::from_static(b"0123456789")).collect();
+            ReqBody::Inner(Box::pin(ChunkedBody { chunks, size_hint }))
+        }
+    }
+    impl Body for ChunkedBody {
+        type Data = Bytes;
+        type Error = BoxedError;
+
+        fn poll_frame(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Frame<Bytes>, BoxedError>>> {
+            Poll::Ready(self.chunks.pop_front().map(|chunk| Ok(Frame::data(chunk))))
+        }
+
+        fn size_hint(&self) -> SizeHint {
+            match self.size_hint {
+                Some(size) => SizeHint::with_exact(size),
+                None => SizeHint::default(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_size_limiter() {
+        let router = Router::new()
+            .hoop(max_size(32))
+            .push(Router::with_path("upload").post(upload))
+            .push(
+                Router::with_path("large")
+                    .hoop(max_size(1024))
+                    .post(upload)
+                    .push(Router::with_path("shrink").hoop(shrink_limit).post(upload)),
+            );
+        let service = Service::new(router);
+        let send = |path: &'static str, body: ReqBody| {
+            TestClient::post(format!("http://127.0.0.1:5801/{path}"))
+                .body(body)
+                .send(&service)
+        };
+
+        let mut res = send("upload", ChunkedBody::body(3, None)).await;
+        assert_eq!(res.take_string().await.unwrap(), "30");
+        let res = send("upload", ChunkedBody::body(4, None)).await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::PAYLOAD_TOO_LARGE);
+        let res = send("upload", ChunkedBody::body(4, Some(10))).await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let mut res = send("large", ChunkedBody::body(100, None)).await;
+        assert_eq!(res.take_string().await.unwrap(), "1000");
+        let mut res = send("large", ChunkedBody::body(10, Some(100))).await;
+        assert_eq!(res.take_string().await.unwrap(), "100");
+        let res = send("large", ChunkedBody::body(10, Some(2000))).await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::PAYLOAD_TOO_LARGE);
+        let res = send("large", ChunkedBody::body(103, None)).await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::PAYLOAD_TOO_LARGE);
+        let res = send("large/shrink", ChunkedBody::body(1, None)).await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
     #[tokio::test]
     async fn test_size_limiter() {
         let limit_handler = MaxSize(32);
         let router = Router::new()
             .hoop(limit_handler)
-            .push(Router::with_path("hello").post(hello));
+            .push(Router::with_path("hello").post(hello))
+            .push(Router::with_path("read").post(read_hello));
         let service = Service::new(router);
 
-        let content = TestClient::post("http://127.0.0.1:5801/hello")
+        let content = TestClient::post("http://127.0.0.1:5801/read")
             .text("abc")
             .send(&service)
             .await
@@ -59,10 +265,18 @@ mod tests {
             .unwrap();
         assert_eq!(content, "hello");
 
-        let res = TestClient::post("http://127.0.0.1:5801/hello")
+        let res = TestClient::post("http://127.0.0.1:5801/read")
             .text("abcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyz")
             .send(&service)
             .await;
         assert_eq!(res.status_code.unwrap(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // The body is never read, so the response of handler is kept.
+        let mut res = TestClient::post("http://127.0.0.1:5801/hello")
+            .text("abcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyz")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::OK);
+        assert_eq!(res.take_string().await.unwrap(), "hello");
     }
 }
@@ -14,6 +14,7 @@ struct FieldInfo {
     sources: Vec<SourceInfo>,
     aliases: Vec<String>,
     rename: Option<String>,
+    validators: Vec<ValidatorInfo>,
 }
 impl TryFrom<&Field> for FieldInfo {
     type Error = Error;
@@ -24,6 +25,7 @@ impl TryFrom<&Field> for FieldInfo {
         let mut sources: Vec<SourceInfo> = Vec::with_capacity(field.attrs.len());
         let mut aliases = Vec::with_capacity(field.attrs.len());
         let mut rename = None;
+        let mut validators = Vec::new();
         for attr in attrs {
             if attr.path().is_ident("extract") {
                 let info: ExtractFieldInfo = attr.parse_args()?;
@@ -32,6 +34,7 @@ impl TryFrom<&Field> for FieldInfo {
                 if info.rename.is_some() {
                     rename = info.rename;
                 }
+                validators.extend(info.validators);
             }
         }
         sources.dedup();
@@ -42,6 +45,7 @@ impl TryFrom<&Field> for FieldInfo {
             sources,
             aliases,
             rename,
+            validators,
         })
     }
 }
@@ -73,11 +77,12 @@ impl Parse for ExtractStructInfo {
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default)]
 struct ExtractFieldInfo {
     sources: Vec<SourceInfo>,
     aliases: Vec<String>,
     rename: Option<String>,
+    validators: Vec<ValidatorInfo>,
 }
 impl Parse for ExtractFieldInfo {
     fn parse(input: ParseStream) -> syn::Result<Self> {
@@ -96,6 +101,10 @@ impl Parse for ExtractFieldInfo {
                 input.parse::<Token![=]>()?;
                 let expr = input.parse::<Expr>()?;
                 extract.aliases.push(expr_lit_value(&expr)?);
+            } else if id == "validate" {
+                let item;
+                syn::parenthesized!(item in input);
+                extract.validators.extend(item.parse::<ValidateFieldInfo>()?.0);
             } else {
                 return Err(input.error("unexpected attribute"));
             }
@@ -105,6 +114,134 @@ impl Parse for ExtractFieldInfo {
     }
 }
 
+enum ValidatorInfo {
+    Length {
+        min: Option<Expr>,
+        max: Option<Expr>,
+    },
+    Range {
+        min: Option<Expr>,
+        max: Option<Expr>,
+    },
+    Regex(String),
+    Email,
+    Required,
+    RequiredIf {
+        field: Ident,
+        value: Expr,
+    },
+    File {
+        max_size: Option<Expr>,
+        max_count: Option<Expr>,
+        content_types: Vec<String>,
+    },
+}
+impl ValidatorInfo {
+    /// Validators which check the value itself, so `None` of `Option` is skipped by them.
+    fn is_inner(&self) -> bool {
+        !matches!(self, Self::Required | Self::RequiredIf { .. })
+    }
+}
+
+struct ValidateFieldInfo(Vec<ValidatorInfo>);
+impl Parse for ValidateFieldInfo {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut validators = Vec::new();
+        while !input.is_empty() {
+            let id = input.parse::<syn::Ident>()?;
+            if id == "length" || id == "range" {
+                let item;
+                syn::parenthesized!(item in input);
+                let (mut min, mut max) = (None, None);
+                let args: Punctuated<MetaNameValue, Token![,]> = Punctuated::parse_terminated(&item)?;
+                for arg in args {
+                    if arg.path.is_ident("min") {
+                        min = Some(arg.value);
+                    } else if arg.path.is_ident("max") {
+                        max = Some(arg.value);
+                    } else {
+                        return Err(Error::new_spanned(arg.path, "expected `min` or `max`"));
+                    }
+                }
+                if id == "length" {
+                    validators.push(ValidatorInfo::Length { min, max });
+                } else {
+                    validators.push(ValidatorInfo::Range { min, max });
+                }
+            } else if id == "regex" {
+                input.parse::<Token![=]>()?;
+                let expr = input.parse::<Expr>()?;
+                let pattern = expr_lit_value(&expr)?;
+                if let Err(e) = regex::Regex::new(&pattern) {
+                    return Err(Error::new_spanned(expr, format!("invalid regex: {e}")));
+                }
+                validators.push(ValidatorInfo::Regex(pattern));
+            } else if id == "email" {
+                validators.push(ValidatorInfo::Email);
+            } else if id == "required" {
+                validators.push(ValidatorInfo::Required);
+            } else if id == "required_if" {
+                let item;
+                syn::parenthesized!(item in input);
+                let (mut field, mut value) = (None, None);
+                let args: Punctuated<MetaNameValue, Token![,]> = Punctuated::parse_terminated(&item)?;
+                for arg in args {
+                    if arg.path.is_ident("field") {
+                        field = Some(Ident::new(&expr_lit_value(&arg.value)?, Span::call_site()));
+                    } else if arg.path.is_ident("value") {
+                        value = Some(arg.value);
+                    } else {
+                        return Err(Error::new_spanned(arg.path, "expected `field` or `value`"));
+                    }
+                }
+                match (field, value) {
+                    (Some(field), Some(value)) => validators.push(ValidatorInfo::RequiredIf { field, value }),
+                    _ => {
+                        return Err(Error::new_spanned(
+                            id,
+                            "`required_if` requires both `field` and `value`",
+                        ))
+                    }
+                }
+            } else if id == "file" {
+                let item;
+                syn::parenthesized!(item in input);
+                let (mut max_size, mut max_count, mut content_types) = (None, None, vec![]);
+                let args: Punctuated<MetaNameValue, Token![,]> = Punctuated::parse_terminated(&item)?;
+                for arg in args {
+                    if arg.path.is_ident("max_size") {
+                        max_size = Some(arg.value);
+                    } else if arg.path.is_ident("max_count") {
+                        max_count = Some(arg.value);
+                    } else if arg.path.is_ident("content_types") {
+                        if let Expr::Array(array) = &arg.value {
+                            for elem in &array.elems {
+                                content_types.push(expr_lit_value(elem)?);
+                            }
+                        } else {
+                            return Err(Error::new_spanned(arg.value, "expected array of content types"));
+                        }
+                    } else {
+                        return Err(Error::new_spanned(
+                            arg.path,
+                            "expected `max_size`, `max_count` or `content_types`",
+                        ));
+                    }
+                }
+                validators.push(ValidatorInfo::File {
+                    max_size,
+                    max_count,
+                    content_types,
+                });
+            } else {
+                return Err(Error::new_spanned(id, "unexpected validator"));
+            }
+            input.parse::<Token![,]>().ok();
+        }
+        Ok(Self(validators))
+    }
+}
+
 #[derive(Eq, PartialEq, Debug)]
 struct SourceInfo {
     from: String,
@@ -135,13 +272,17 @@ impl Parse for SourceInfo {
                 source.format = "multimap".to_string();
             }
         }
-        if !["request", "param", "query", "header", "body"].contains(&source.from.as_str()) {
+        if !["request", "param", "query", "header", "cookie", "body"].contains(&source.from.as_str()) {
             return Err(Error::new(
                 input.span(),
                 format!("source from is invalid: {}", source.from),
             ));
         }
-        if !["multimap", "json", "request"].contains(&source.format.as_str()) {
+        if ![
+            "multimap", "json", "request", "signed", "private", "msgpack", "cbor", "xml",
+        ]
+        .contains(&source.format.as_str())
+        {
             return Err(Error::new(
                 input.span(),
                 format!("source format is invalid: {}", source.format),
@@ -153,6 +294,18 @@ impl Parse for SourceInfo {
                 "source format must be `request` for `request` sources",
             ));
         }
+        if ["signed", "private"].contains(&source.format.as_str()) && source.from != "cookie" {
+            return Err(Error::new(
+                input.span(),
+                "source format `signed` and `private` are only supported for `cookie` sources",
+            ));
+        }
+        if ["msgpack", "cbor", "xml"].contains(&source.format.as_str()) && source.from != "body" {
+            return Err(Error::new(
+                input.span(),
+                "source format `msgpack`, `cbor` and `xml` are only supported for `body` sources",
+            ));
+        }
         Ok(source)
     }
 }
@@ -241,6 +394,8 @@ fn metadata_source(salvo: &Ident, source: &SourceInfo) -> TokenStream {
     let from = Ident::new(&source.from.to_pascal_case(), Span::call_site());
     let format = if source.format.to_lowercase() == "multimap" {
         Ident::new("MultiMap", Span::call_site())
+    } else if source.format.to_lowercase() == "msgpack" {
+        Ident::new("MsgPack", Span::call_site())
     } else {
         Ident::new(&source.format.to_pascal_case(), Span::call_site())
     };
@@ -261,8 +416,10 @@ pub(crate) fn generate(args: DeriveInput) -> Result<TokenStream, Error> {
     let (impl_generics, ty_generics, where_clause) = args.generics.split_for_impl();
 
     let name = &args.ident;
+    let sv: Ident = format_ident!("__salvo_extract_{}", name);
     let mut default_sources = Vec::new();
     let mut fields = Vec::new();
+    let mut validations = Vec::new();
 
     for source in &args.default_sources {
         let source = metadata_source(&salvo, source);
@@ -296,6 +453,12 @@ pub(crate) fn generate(args: DeriveInput) -> Result<TokenStream, Error> {
                     nested_metadata = Some(quote! {
                         field = field.metadata(<#ty as #salvo::extract::Extractible>::metadata());
                     });
+                    let ident = &field.ident;
+                    validations.push(quote! {
+                        if let Err(params) = <#ty as #salvo::extract::Extractible>::validate(&self.#ident) {
+                            invalid_params.extend(params);
+                        }
+                    });
                 } else {
                     return Err(Error::new_spanned(name, "Invalid type for request source."));
                 }
@@ -318,6 +481,9 @@ pub(crate) fn generate(args: DeriveInput) -> Result<TokenStream, Error> {
                 field = field.rename(#rename);
             }
         });
+        if !field.validators.is_empty() {
+            validations.push(field_validation(&salvo, &sv, field, &field_ident));
+        }
         fields.push(quote! {
             let mut field = #salvo::extract::metadata::Field::new(#field_ident);
             #nested_metadata
@@ -328,8 +494,22 @@ pub(crate) fn generate(args: DeriveInput) -> Result<TokenStream, Error> {
         });
     }
 
-    let sv: Ident = format_ident!("__salvo_extract_{}", name);
     let mt = name.to_string();
+    let validate_fn = if validations.is_empty() {
+        None
+    } else {
+        Some(quote! {
+            fn validate(&self) -> Result<(), Vec<#salvo::http::errors::InvalidParam>> {
+                let mut invalid_params = Vec::new();
+                #(#validations)*
+                if invalid_params.is_empty() {
+                    Ok(())
+                } else {
+                    Err(invalid_params)
+                }
+            }
+        })
+    };
     let imp_code = if args.generics.lifetimes().next().is_none() {
         let de_life_def = syn::parse_str("'de").unwrap();
         let mut generics = args.generics.clone();
@@ -340,6 +520,7 @@ pub(crate) fn generate(args: DeriveInput) -> Result<TokenStream, Error> {
                 fn metadata() ->  &'static #salvo::extract::Metadata {
                     &*#sv
                 }
+                #validate_fn
             }
         }
     } else {
@@ -348,6 +529,7 @@ pub(crate) fn generate(args: DeriveInput) -> Result<TokenStream, Error> {
                 fn metadata() ->  &'static #salvo::extract::Metadata {
                     &*#sv
                 }
+                #validate_fn
             }
         }
     };
@@ -369,6 +551,91 @@ pub(crate) fn generate(args: DeriveInput) -> Result<TokenStream, Error> {
     Ok(code)
 }
 
+fn field_validation(salvo: &Ident, sv: &Ident, field: &FieldInfo, field_ident: &str) -> TokenStream {
+    let ident = &field.ident;
+    let checks = |validators: Vec<&ValidatorInfo>| {
+        validators
+            .into_iter()
+            .map(|validator| {
+                let check = match validator {
+                    ValidatorInfo::Length { min, max } => {
+                        let (min, max) = (option_tokens(min), option_tokens(max));
+                        quote! { #salvo::extract::validate::length(value, #min, #max) }
+                    }
+                    ValidatorInfo::Range { min, max } => {
+                        let (min, max) = (option_tokens(min), option_tokens(max));
+                        quote! { #salvo::extract::validate::range(value, #min, #max) }
+                    }
+                    ValidatorInfo::Regex(pattern) => quote! {{
+                        static REGEX: #salvo::__private::once_cell::sync::Lazy<#salvo::__private::regex::Regex> =
+                            #salvo::__private::once_cell::sync::Lazy::new(|| #salvo::__private::regex::Regex::new(#pattern).unwrap());
+                        #salvo::extract::validate::regex(value, &REGEX)
+                    }},
+                    ValidatorInfo::Email => quote! { #salvo::extract::validate::email(value) },
+                    ValidatorInfo::File {
+                        max_size,
+                        max_count,
+                        content_types,
+                    } => {
+                        let (max_size, max_count) = (option_tokens(max_size), option_tokens(max_count));
+                        quote! { #salvo::extract::validate::file(value, #max_size, #max_count, &[#(#content_types),*]) }
+                    }
+                    ValidatorInfo::Required => quote! { #salvo::extract::validate::required(&self.#ident) },
+                    ValidatorInfo::RequiredIf { field, value } => quote! {
+                        if self.#field == #value {
+                            #salvo::extract::validate::required(&self.#ident)
+                        } else {
+                            Ok(())
+                        }
+                    },
+                };
+                quote! {
+                    if let Err(reason) = #check {
+                        invalid_params.push(#salvo::extract::validate::invalid_param(&*#sv, #field_ident, reason));
+                    }
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let outer_checks = checks(field.validators.iter().filter(|v| !v.is_inner()).collect());
+    let inner_checks = checks(field.validators.iter().filter(|v| v.is_inner()).collect());
+    let inner = if inner_checks.is_empty() {
+        None
+    } else if is_option_type(&field.ty) {
+        Some(quote! {
+            if let Some(value) = &self.#ident {
+                #(#inner_checks)*
+            }
+        })
+    } else {
+        Some(quote! {
+            let value = &self.#ident;
+            #(#inner_checks)*
+        })
+    };
+    quote! {
+        {
+            #(#outer_checks)*
+            #inner
+        }
+    }
+}
+
+fn option_tokens(expr: &Option<Expr>) -> TokenStream {
+    match expr {
+        Some(expr) => quote! { Some(#expr) },
+        None => quote! { None },
+    }
+}
+
+fn is_option_type(ty: &Type) -> bool {
+    if let Type::Path(ty) = ty {
+        ty.path.segments.last().map(|s| s.ident == "Option").unwrap_or(false)
+    } else {
+        false
+    }
+}
+
 fn expr_lit_value(expr: &Expr) -> syn::Result<String> {
     if let Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) = expr {
         Ok(s.value())
@@ -43,7 +43,8 @@ pub mod server;
 mod tag;
 mod xml;
 
-use crate::{router::NormNode, Endpoint};
+use crate::router::{norm_path, path_item_type};
+use crate::Endpoint;
 
 static PARAMETER_NAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\{([^]]+)\}"#).unwrap());
 
@@ -235,12 +236,6 @@ impl OpenApi {
 
     /// Consusmes the [`OpenApi`] and informations from a [`Router`] with base path.
     pub fn merge_router_with_base(mut self, router: &Router, base: impl AsRef<str>) -> Self {
-        let mut node = NormNode::new(router);
-        self.merge_norm_node(&mut node, base.as_ref());
-        self
-    }
-
-    fn merge_norm_node(&mut self, node: &mut NormNode, base_path: &str) {
         fn join_path(a: &str, b: &str) -> String {
             if a.is_empty() {
                 b.to_owned()
@@ -251,54 +246,53 @@ impl OpenApi {
             }
         }
 
-        let path = join_path(base_path, node.path.as_deref().unwrap_or_default());
-        let parameter_names = PARAMETER_NAME_REGEX
-            .captures(&path)
-            .map(|captures| {
-                captures
-                    .iter()
-                    .skip(1)
-                    .map(|capture| capture.unwrap().as_str().to_owned())
-                    .collect::<Vec<_>>()
-            })
-            .unwrap_or_default();
-        if let Some(type_id) = &node.type_id {
-            if let Some(creator) = crate::EndpointRegistry::find(type_id) {
-                let Endpoint {
-                    operation,
-                    mut components,
-                } = (creator)();
-                let methods = if let Some(method) = &node.method {
-                    vec![*method]
+        for route in router.routes() {
+            let Some(creator) = crate::EndpointRegistry::find(&route.handler_type_id) else {
+                continue;
+            };
+            let path = norm_path(&join_path(base.as_ref(), &route.path));
+            let parameter_names = PARAMETER_NAME_REGEX
+                .captures(&path)
+                .map(|captures| {
+                    captures
+                        .iter()
+                        .skip(1)
+                        .map(|capture| capture.unwrap().as_str().to_owned())
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            let Endpoint {
+                operation,
+                mut components,
+            } = (creator)();
+            let methods = if route.methods.is_empty() {
+                vec![
+                    PathItemType::Get,
+                    PathItemType::Post,
+                    PathItemType::Put,
+                    PathItemType::Patch,
+                ]
+            } else {
+                route.methods.iter().filter_map(path_item_type).collect()
+            };
+            let not_exist_parameters = parameter_names
+                .iter()
+                .filter(|name| !operation.parameters.0.iter().any(|parameter| parameter.name == **name))
+                .collect::<Vec<_>>();
+            if !not_exist_parameters.is_empty() {
+                tracing::warn!(parameters = ?not_exist_parameters, path, "parameters not found in operation");
+            }
+            let path_item = self.paths.entry(path.clone()).or_default();
+            for method in methods {
+                if let btree_map::Entry::Vacant(e) = path_item.operations.entry(method) {
+                    e.insert(operation.clone());
                 } else {
-                    vec![
-                        PathItemType::Get,
-                        PathItemType::Post,
-                        PathItemType::Put,
-                        PathItemType::Patch,
-                    ]
-                };
-                let not_exist_parameters = parameter_names
-                    .iter()
-                    .filter(|name| !operation.parameters.0.iter().any(|parameter| parameter.name == **name))
-                    .collect::<Vec<_>>();
-                if !not_exist_parameters.is_empty() {
-                    tracing::warn!(parameters = ?not_exist_parameters, path, "parameters not found in operation");
-                }
-                let path_item = self.paths.entry(path.clone()).or_default();
-                for method in methods {
-                    if let btree_map::Entry::Vacant(e) = path_item.operations.entry(method) {
-                        e.insert(operation.clone());
-                    } else {
-                        tracing::warn!("path `{}` already contains operation for method `{:?}`", path, method);
-                    }
+                    tracing::warn!("path `{}` already contains operation for method `{:?}`", path, method);
                 }
-                self.components.append(&mut components);
             }
+            self.components.append(&mut components);
         }
-        for child in &mut node.children {
-            self.merge_norm_node(child, &path);
-        }
+        self
     }
 }
 
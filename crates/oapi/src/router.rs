@@ -1,49 +1,39 @@
-use std::any::TypeId;
-
+use once_cell::sync::Lazy;
 use regex::Regex;
-use salvo_core::Router;
+use salvo_core::http::Method;
 
 use crate::path::PathItemType;
 
-#[derive(Debug, Default)]
-pub(crate) struct NormNode {
-    pub(crate) type_id: Option<TypeId>,
-    pub(crate) method: Option<PathItemType>,
-    pub(crate) path: Option<String>,
-    pub(crate) children: Vec<NormNode>,
+static PATH_PARAMETER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"<([^/:>]+)(:[^>]*)?>"#).unwrap());
+
+/// Convert salvo's path template to openapi's path template, `<id:num>` will be converted to `{id}`.
+pub(crate) fn norm_path(path: &str) -> String {
+    PATH_PARAMETER_REGEX.replace_all(path, "{$1}").to_string()
+}
+
+/// Convert http method to [`PathItemType`].
+pub(crate) fn path_item_type(method: &Method) -> Option<PathItemType> {
+    match *method {
+        Method::GET => Some(PathItemType::Get),
+        Method::POST => Some(PathItemType::Post),
+        Method::PUT => Some(PathItemType::Put),
+        Method::DELETE => Some(PathItemType::Delete),
+        Method::HEAD => Some(PathItemType::Head),
+        Method::OPTIONS => Some(PathItemType::Options),
+        Method::CONNECT => Some(PathItemType::Connect),
+        Method::TRACE => Some(PathItemType::Trace),
+        Method::PATCH => Some(PathItemType::Patch),
+        _ => None,
+    }
 }
 
-impl NormNode {
-    pub(crate) fn new(router: &Router) -> Self {
-        let mut node = NormNode::default();
-        let regex = Regex::new(r#"<([^/:>]+)(:[^>]*)?>"#).unwrap();
-        for filter in router.filters() {
-            let info = format!("{filter:?}");
-            if info.starts_with("path:") {
-                let path = info.split_once(':').unwrap().1;
-                node.path = Some(regex.replace(path, "{$1}").to_string());
-            } else if info.starts_with("method:") {
-                match info.split_once(':').unwrap().1 {
-                    "GET" => node.method = Some(PathItemType::Get),
-                    "POST" => node.method = Some(PathItemType::Post),
-                    "PUT" => node.method = Some(PathItemType::Put),
-                    "DELETE" => node.method = Some(PathItemType::Delete),
-                    "HEAD" => node.method = Some(PathItemType::Head),
-                    "OPTIONS" => node.method = Some(PathItemType::Options),
-                    "CONNECT" => node.method = Some(PathItemType::Connect),
-                    "TRACE" => node.method = Some(PathItemType::Trace),
-                    "PATCH" => node.method = Some(PathItemType::Patch),
-                    _ => {}
-                }
-            }
-        }
-        node.type_id = router.handler.as_ref().map(|h| h.type_id());
-        let routers = router.routers();
-        if !routers.is_empty() {
-            for router in routers {
-                node.children.push(NormNode::new(router));
-            }
-        }
-        node
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_norm_path() {
+        assert_eq!(norm_path("/users/<id:num>/emails/<eid>"), "/users/{id}/emails/{eid}");
+        assert_eq!(norm_path("/users"), "/users");
     }
 }
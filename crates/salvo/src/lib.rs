@@ -122,6 +122,11 @@ cfg_feature! {
     #[doc(no_inline)]
     pub use salvo_serve_static as serve_static;
 }
+cfg_feature! {
+    #![feature ="template"]
+    #[doc(no_inline)]
+    pub use salvo_template as template;
+}
 cfg_feature! {
     #![feature ="otel"]
     #[doc(no_inline)]
@@ -154,7 +159,7 @@ pub mod prelude {
     }
     cfg_feature! {
         #![feature ="compression"]
-        pub use salvo_extra::compression::{Compression, CompressionAlgo, CompressionLevel};
+        pub use salvo_extra::compression::{Compression, CompressionAlgo, CompressionLevel, Decompression};
     }
     cfg_feature! {
         #![feature ="csrf"]
@@ -182,7 +187,7 @@ pub mod prelude {
     }
     cfg_feature! {
         #![feature ="size-limiter"]
-        pub use salvo_extra::size_limiter::max_size;
+        pub use salvo_extra::size_limiter::{max_size, MaxSizeDepotExt};
     }
     cfg_feature! {
         #![feature ="sse"]
@@ -204,6 +209,10 @@ pub mod prelude {
         #![feature ="serve-static"]
         pub use salvo_serve_static::{StaticFile, StaticDir};
     }
+    cfg_feature! {
+        #![feature ="template"]
+        pub use salvo_template::{Template, TemplateHandler};
+    }
     cfg_feature! {
         #![feature ="oapi"]
         pub use salvo_oapi::endpoint;
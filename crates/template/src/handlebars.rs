@@ -0,0 +1,118 @@
+use std::path::{Path, PathBuf};
+
+use ::handlebars::{Handlebars, TemplateError};
+use parking_lot::RwLock;
+use salvo_core::BoxedError;
+
+use crate::watcher::{walk_files, DirWatcher};
+use crate::{Context, TemplateEngine};
+
+/// Template engine adapter for [Handlebars](https://github.com/sunng87/handlebars-rust).
+#[derive(Debug)]
+pub struct HandlebarsEngine {
+    registry: RwLock<Handlebars<'static>>,
+    watcher: Option<DirWatcher>,
+    dir: PathBuf,
+    extension: String,
+}
+impl HandlebarsEngine {
+    /// Create a new `HandlebarsEngine` with a configured `Handlebars`, templates are never reloaded.
+    #[inline]
+    pub fn new(registry: Handlebars<'static>) -> Self {
+        Self {
+            registry: RwLock::new(registry),
+            watcher: None,
+            dir: PathBuf::new(),
+            extension: String::new(),
+        }
+    }
+
+    /// Load all the templates with the extension (such as `.hbs`) in the directory, template names are the paths
+    /// relative to the directory without the extension.
+    ///
+    /// Templates are reloaded when files in the directory are changed in debug builds, use
+    /// [`hot_reload`](Self::hot_reload) to change it.
+    #[allow(clippy::result_large_err)]
+    pub fn from_dir(dir: impl AsRef<Path>, extension: impl Into<String>) -> Result<Self, TemplateError> {
+        let dir = dir.as_ref();
+        let extension = extension.into();
+        let mut registry = Handlebars::new();
+        register_dir(&mut registry, dir, &extension)?;
+        Ok(Self {
+            registry: RwLock::new(registry),
+            watcher: Some(DirWatcher::new(dir)),
+            dir: dir.to_owned(),
+            extension,
+        })
+    }
+
+    /// Enable or disable reloading templates when files in the directory are changed.
+    ///
+    /// It only works for engine created by [`from_dir`](Self::from_dir).
+    #[inline]
+    pub fn hot_reload(mut self, enabled: bool) -> Self {
+        if let Some(watcher) = &mut self.watcher {
+            watcher.set_enabled(enabled);
+        }
+        self
+    }
+
+    /// Get a mutable reference of inner `Handlebars` to register helpers, partials and so on.
+    #[inline]
+    pub fn registry_mut(&mut self) -> &mut Handlebars<'static> {
+        self.registry.get_mut()
+    }
+}
+impl TemplateEngine for HandlebarsEngine {
+    fn render(&self, name: &str, context: &Context) -> Result<String, BoxedError> {
+        if let Some(watcher) = &self.watcher {
+            if watcher.changed() {
+                let mut registry = self.registry.write();
+                registry.clear_templates();
+                register_dir(&mut registry, &self.dir, &self.extension)?;
+            }
+        }
+        Ok(self.registry.read().render(name, context)?)
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn register_dir(registry: &mut Handlebars<'static>, dir: &Path, extension: &str) -> Result<(), TemplateError> {
+    for path in walk_files(dir) {
+        let name = match path.strip_prefix(dir).ok().and_then(|name| name.to_str()) {
+            Some(name) => name.replace('\\', "/"),
+            None => continue,
+        };
+        if let Some(name) = name.strip_suffix(extension) {
+            registry.register_template_file(name, &path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_handlebars_engine() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("users")).unwrap();
+        fs::write(dir.path().join("users/show.hbs"), "<h1>Hello {{name}}</h1>").unwrap();
+        fs::write(dir.path().join("README.md"), "not a template").unwrap();
+        let engine = HandlebarsEngine::from_dir(dir.path(), ".hbs").unwrap().hot_reload(true);
+        let mut context = Context::new();
+        context.insert("name", "salvo");
+        assert_eq!(engine.render("users/show", &context).unwrap(), "<h1>Hello salvo</h1>");
+        assert!(engine.render("README", &context).is_err());
+
+        fs::write(dir.path().join("users/show.hbs"), "<h2>Hello {{name}}!</h2>").unwrap();
+        assert_eq!(engine.render("users/show", &context).unwrap(), "<h2>Hello salvo!</h2>");
+
+        let engine = engine.hot_reload(false);
+        fs::write(dir.path().join("users/show.hbs"), "Hello {{name}}").unwrap();
+        assert_eq!(engine.render("users/show", &context).unwrap(), "<h2>Hello salvo!</h2>");
+    }
+}
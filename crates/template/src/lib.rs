@@ -0,0 +1,379 @@
+//! The template lib for Savlo web server framework. Read more: <https://salvo.rs>
+#![doc(html_favicon_url = "https://salvo.rs/favicon-32x32.png")]
+#![doc(html_logo_url = "https://salvo.rs/images/logo.svg")]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![deny(private_in_public, unreachable_pub)]
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+#![warn(clippy::future_not_send)]
+#![warn(rustdoc::broken_intra_doc_links)]
+
+#[macro_use]
+mod cfg;
+
+use std::fmt::{self, Formatter};
+use std::sync::Arc;
+
+use salvo_core::http::StatusError;
+use salvo_core::writer::Text;
+use salvo_core::{async_trait, BoxedError, Depot, FlowCtrl, Handler, Request, Response, Writer};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+#[cfg(any(feature = "tera", feature = "minijinja", feature = "handlebars"))]
+mod watcher;
+
+cfg_feature! {
+    #![feature = "tera"]
+
+    mod tera;
+    pub use self::tera::TeraEngine;
+}
+cfg_feature! {
+    #![feature = "minijinja"]
+
+    mod minijinja;
+    pub use self::minijinja::MiniJinjaEngine;
+}
+cfg_feature! {
+    #![feature = "handlebars"]
+
+    mod handlebars;
+    pub use self::handlebars::HandlebarsEngine;
+}
+
+/// Template engine used by [`Template`] to render templates.
+///
+/// Built-in adapters are `TeraEngine`, `MiniJinjaEngine` and `HandlebarsEngine`, they are enabled by the
+/// features with the same name of the engines.
+pub trait TemplateEngine: Send + Sync + 'static {
+    /// Render the template with the given name.
+    fn render(&self, name: &str, context: &Context) -> Result<String, BoxedError>;
+}
+
+/// Values which are accessible in templates.
+#[derive(Serialize, Clone, Default, Debug)]
+#[serde(transparent)]
+pub struct Context(Map<String, Value>);
+impl Context {
+    /// Create a new empty `Context`.
+    #[inline]
+    pub fn new() -> Self {
+        Self(Map::new())
+    }
+
+    /// Create a `Context` from a serializable value, the value must be serialized to a map.
+    pub fn from_serialize(value: impl Serialize) -> Result<Self, serde_json::Error> {
+        match serde_json::to_value(value)? {
+            Value::Object(map) => Ok(Self(map)),
+            _ => Err(serde::ser::Error::custom("context must be serialized to a map")),
+        }
+    }
+
+    /// Insert a value into the context, the value with the same key is replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value can not be serialized.
+    #[inline]
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Serialize) -> &mut Self {
+        let value = serde_json::to_value(value).expect("context value must be serializable");
+        self.0.insert(key.into(), value);
+        self
+    }
+
+    /// Get a value from the context.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0.get(key)
+    }
+
+    /// Returns `true` if the context contains the key.
+    #[inline]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    /// Move all values of other context into this one, values with the same keys are replaced.
+    #[inline]
+    pub fn extend(&mut self, other: Context) -> &mut Self {
+        self.0.extend(other.0);
+        self
+    }
+
+    /// Consume the context and returns the inner map.
+    #[inline]
+    pub fn into_inner(self) -> Map<String, Value> {
+        self.0
+    }
+}
+
+/// Function which injects values in `Depot` into the context before rendering.
+pub type Injector = dyn Fn(&mut Depot, &mut Context) + Send + Sync;
+
+/// `TemplateHandler` makes the template engine available to [`Template`] in the handlers after it.
+///
+/// Values in `Depot` are injected into the context of every rendered template:
+/// - `csrf_token`: the token generated by `salvo-csrf`'s middleware (`csrf` feature).
+/// - `flash`: the incoming messages loaded by `salvo-flash`'s middleware (`flash` feature).
+///
+/// # Example
+///
+/// ```no_run
+/// use salvo_core::prelude::*;
+/// use salvo_template::{Template, TemplateHandler, TeraEngine};
+///
+/// #[handler]
+/// async fn index() -> Template {
+///     Template::new("index.html").with("name", "salvo")
+/// }
+///
+/// let engine = TeraEngine::from_dir("templates").unwrap();
+/// let router = Router::new().hoop(TemplateHandler::new(engine)).get(index);
+/// ```
+#[derive(Clone)]
+pub struct TemplateHandler {
+    engine: Arc<dyn TemplateEngine>,
+    injectors: Vec<Arc<Injector>>,
+}
+impl fmt::Debug for TemplateHandler {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TemplateHandler")
+            .field("injectors", &self.injectors.len())
+            .finish()
+    }
+}
+impl TemplateHandler {
+    /// Create a new `TemplateHandler` with the template engine.
+    pub fn new(engine: impl TemplateEngine) -> Self {
+        #[allow(unused_mut)]
+        let mut injectors: Vec<Arc<Injector>> = vec![];
+        #[cfg(feature = "csrf")]
+        injectors.push(Arc::new(|depot: &mut Depot, context: &mut Context| {
+            use salvo_csrf::CsrfDepotExt;
+            if let Some(token) = depot.csrf_token() {
+                context.insert("csrf_token", token);
+            }
+        }));
+        #[cfg(feature = "flash")]
+        injectors.push(Arc::new(|depot: &mut Depot, context: &mut Context| {
+            use salvo_flash::FlashDepotExt;
+            if let Some(flash) = depot.incoming_flash() {
+                context.insert("flash", flash);
+            }
+        }));
+        Self {
+            engine: Arc::new(engine),
+            injectors,
+        }
+    }
+
+    /// Add an injector which injects values into the context before rendering.
+    ///
+    /// Values set by [`Template::with`] are not replaced by the injected values.
+    #[inline]
+    pub fn inject<F>(mut self, injector: F) -> Self
+    where
+        F: Fn(&mut Depot, &mut Context) + Send + Sync + 'static,
+    {
+        self.injectors.push(Arc::new(injector));
+        self
+    }
+
+    /// Get the template engine.
+    #[inline]
+    pub fn engine(&self) -> &dyn TemplateEngine {
+        &*self.engine
+    }
+}
+#[async_trait]
+impl Handler for TemplateHandler {
+    #[inline]
+    async fn handle(&self, _req: &mut Request, depot: &mut Depot, _res: &mut Response, _ctrl: &mut FlowCtrl) {
+        depot.inject(self.clone());
+    }
+}
+
+/// Render a template to response as HTML.
+///
+/// [`TemplateHandler`] must be added before the handler which renders it.
+#[derive(Clone, Debug)]
+pub struct Template {
+    name: String,
+    context: Context,
+}
+impl Template {
+    /// Create a new `Template` with the template name.
+    #[inline]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            context: Context::new(),
+        }
+    }
+
+    /// Insert a value into the context and returns `Self` for write code chained.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value can not be serialized.
+    #[inline]
+    pub fn with(mut self, key: impl Into<String>, value: impl Serialize) -> Self {
+        self.context.insert(key, value);
+        self
+    }
+
+    /// Move all values of the context into the template's context and returns `Self` for write code chained.
+    #[inline]
+    pub fn with_context(mut self, context: Context) -> Self {
+        self.context.extend(context);
+        self
+    }
+
+    /// Get the template name.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the template context.
+    #[inline]
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+}
+#[async_trait]
+impl Writer for Template {
+    async fn write(self, _req: &mut Request, depot: &mut Depot, res: &mut Response) {
+        let handler = match depot.obtain::<TemplateHandler>() {
+            Some(handler) => handler.clone(),
+            None => {
+                tracing::error!("TemplateHandler is not found in depot");
+                res.render(StatusError::internal_server_error());
+                return;
+            }
+        };
+        let mut context = Context::new();
+        for injector in &handler.injectors {
+            injector(depot, &mut context);
+        }
+        context.extend(self.context);
+        match handler.engine.render(&self.name, &context) {
+            Ok(content) => {
+                res.render(Text::Html(content));
+            }
+            Err(e) => {
+                tracing::error!(error = ?e, name = %self.name, "render template failed");
+                res.render(StatusError::internal_server_error());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::http::header::CONTENT_TYPE;
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+    use serde_json::json;
+
+    use super::*;
+
+    struct EchoEngine;
+    impl TemplateEngine for EchoEngine {
+        fn render(&self, name: &str, context: &Context) -> Result<String, BoxedError> {
+            Ok(format!("{name}: {}", serde_json::to_string(context)?))
+        }
+    }
+
+    /// Parse the context echoed by `EchoEngine` for `name`, so comparisons do not depend on key order.
+    async fn echoed(res: &mut Response, name: &str) -> serde_json::Value {
+        let body = res.take_string().await.unwrap();
+        let json = body.strip_prefix(&format!("{name}: ")).unwrap();
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[handler]
+    async fn index() -> Template {
+        Template::new("index.html").with("name", "salvo").with("count", 2)
+    }
+
+    #[handler]
+    async fn set_user(depot: &mut Depot) {
+        depot.insert("user", "jobs");
+    }
+
+    #[test]
+    fn test_context() {
+        #[derive(Serialize)]
+        struct User {
+            name: String,
+        }
+        let mut context = Context::from_serialize(User { name: "jobs".into() }).unwrap();
+        context.insert("age", 56);
+        assert_eq!(context.get("name"), Some(&Value::from("jobs")));
+        assert!(context.contains_key("age"));
+        assert!(Context::from_serialize(vec![1, 2]).is_err());
+
+        let mut other = Context::new();
+        other.insert("name", "steve");
+        context.extend(other);
+        assert_eq!(
+            serde_json::to_value(&context).unwrap(),
+            json!({"age": 56, "name": "steve"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_render_template() {
+        let handler = TemplateHandler::new(EchoEngine).inject(|depot: &mut Depot, context: &mut Context| {
+            if let Some(user) = depot.get::<&str>("user") {
+                context.insert("user", user);
+            }
+            context.insert("name", "injected");
+        });
+        let router = Router::new().hoop(handler).hoop(set_user).get(index);
+        let mut res = TestClient::get("http://127.0.0.1:5800/").send(router).await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "text/html; charset=utf-8");
+        assert_eq!(
+            echoed(&mut res, "index.html").await,
+            json!({"count": 2, "name": "salvo", "user": "jobs"})
+        );
+
+        let res = TestClient::get("http://127.0.0.1:5800/")
+            .send(Router::new().get(index))
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    #[cfg(all(feature = "csrf", feature = "flash"))]
+    #[tokio::test]
+    async fn test_inject_csrf_and_flash() {
+        use salvo_flash::{Flash, INCOMING_FLASH_KEY};
+
+        #[handler]
+        async fn set_token_and_flash(depot: &mut Depot) {
+            depot.insert(salvo_csrf::CSRF_TOKEN_KEY, "token".to_owned());
+            let mut flash = Flash::default();
+            flash.info("hello");
+            depot.insert(INCOMING_FLASH_KEY, flash);
+        }
+
+        let router = Router::new()
+            .hoop(TemplateHandler::new(EchoEngine))
+            .hoop(set_token_and_flash)
+            .get(index);
+        let mut res = TestClient::get("http://127.0.0.1:5800/").send(router).await;
+        assert_eq!(
+            echoed(&mut res, "index.html").await,
+            json!({
+                "count": 2,
+                "csrf_token": "token",
+                "flash": [{"level": "Info", "value": "hello"}],
+                "name": "salvo"
+            })
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use std::path::Path;
+
+use ::minijinja::{path_loader, Environment};
+use parking_lot::RwLock;
+use salvo_core::BoxedError;
+
+use crate::watcher::DirWatcher;
+use crate::{Context, TemplateEngine};
+
+/// Template engine adapter for [MiniJinja](https://github.com/mitsuhiko/minijinja).
+#[derive(Debug)]
+pub struct MiniJinjaEngine {
+    env: RwLock<Environment<'static>>,
+    watcher: Option<DirWatcher>,
+}
+impl MiniJinjaEngine {
+    /// Create a new `MiniJinjaEngine` with a configured `Environment`, templates are never reloaded.
+    #[inline]
+    pub fn new(env: Environment<'static>) -> Self {
+        Self {
+            env: RwLock::new(env),
+            watcher: None,
+        }
+    }
+
+    /// Load templates from the directory on demand, template names are the paths relative to the directory.
+    ///
+    /// Templates are reloaded when files in the directory are changed in debug builds, use
+    /// [`hot_reload`](Self::hot_reload) to change it.
+    pub fn from_dir(dir: impl AsRef<Path>) -> Self {
+        let dir = dir.as_ref();
+        let mut env = Environment::new();
+        env.set_loader(path_loader(dir));
+        Self {
+            env: RwLock::new(env),
+            watcher: Some(DirWatcher::new(dir)),
+        }
+    }
+
+    /// Enable or disable reloading templates when files in the directory are changed.
+    ///
+    /// It only works for engine created by [`from_dir`](Self::from_dir).
+    #[inline]
+    pub fn hot_reload(mut self, enabled: bool) -> Self {
+        if let Some(watcher) = &mut self.watcher {
+            watcher.set_enabled(enabled);
+        }
+        self
+    }
+
+    /// Get a mutable reference of inner `Environment` to register filters, functions and so on.
+    #[inline]
+    pub fn env_mut(&mut self) -> &mut Environment<'static> {
+        self.env.get_mut()
+    }
+}
+impl TemplateEngine for MiniJinjaEngine {
+    fn render(&self, name: &str, context: &Context) -> Result<String, BoxedError> {
+        if let Some(watcher) = &self.watcher {
+            if watcher.changed() {
+                self.env.write().clear_templates();
+            }
+        }
+        let env = self.env.read();
+        Ok(env.get_template(name)?.render(context)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_minijinja_engine() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("base.html"), "<h1>{% block title %}{% endblock %}</h1>").unwrap();
+        fs::create_dir(dir.path().join("users")).unwrap();
+        fs::write(
+            dir.path().join("users/show.html"),
+            r#"{% extends "base.html" %}{% block title %}Hello {{ name }}{% endblock %}"#,
+        )
+        .unwrap();
+        let engine = MiniJinjaEngine::from_dir(dir.path()).hot_reload(true);
+        let mut context = Context::new();
+        context.insert("name", "salvo");
+        assert_eq!(engine.render("users/show.html", &context).unwrap(), "<h1>Hello salvo</h1>");
+
+        fs::write(dir.path().join("base.html"), "<h2>{% block title %}{% endblock %}!</h2>").unwrap();
+        assert_eq!(engine.render("users/show.html", &context).unwrap(), "<h2>Hello salvo!</h2>");
+        assert!(engine.render("missing.html", &context).is_err());
+
+        let engine = engine.hot_reload(false);
+        fs::write(dir.path().join("base.html"), "{% block title %}{% endblock %}").unwrap();
+        assert_eq!(engine.render("users/show.html", &context).unwrap(), "<h2>Hello salvo!</h2>");
+    }
+}
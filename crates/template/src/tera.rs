@@ -0,0 +1,97 @@
+use std::path::Path;
+
+use ::tera::Tera;
+use parking_lot::RwLock;
+use salvo_core::BoxedError;
+
+use crate::watcher::DirWatcher;
+use crate::{Context, TemplateEngine};
+
+/// Template engine adapter for [Tera](https://keats.github.io/tera/).
+#[derive(Debug)]
+pub struct TeraEngine {
+    tera: RwLock<Tera>,
+    watcher: Option<DirWatcher>,
+}
+impl TeraEngine {
+    /// Create a new `TeraEngine` with a configured `Tera`, templates are never reloaded.
+    #[inline]
+    pub fn new(tera: Tera) -> Self {
+        Self {
+            tera: RwLock::new(tera),
+            watcher: None,
+        }
+    }
+
+    /// Load all the templates in the directory, template names are the paths relative to the directory.
+    ///
+    /// Templates are reloaded when files in the directory are changed in debug builds, use
+    /// [`hot_reload`](Self::hot_reload) to change it.
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self, ::tera::Error> {
+        let dir = dir.as_ref();
+        let tera = Tera::new(&format!("{}/**/*", dir.display()))?;
+        Ok(Self {
+            tera: RwLock::new(tera),
+            watcher: Some(DirWatcher::new(dir)),
+        })
+    }
+
+    /// Enable or disable reloading templates when files in the directory are changed.
+    ///
+    /// It only works for engine created by [`from_dir`](Self::from_dir).
+    #[inline]
+    pub fn hot_reload(mut self, enabled: bool) -> Self {
+        if let Some(watcher) = &mut self.watcher {
+            watcher.set_enabled(enabled);
+        }
+        self
+    }
+
+    /// Get a mutable reference of inner `Tera` to register filters, functions and so on.
+    #[inline]
+    pub fn tera_mut(&mut self) -> &mut Tera {
+        self.tera.get_mut()
+    }
+}
+impl TemplateEngine for TeraEngine {
+    fn render(&self, name: &str, context: &Context) -> Result<String, BoxedError> {
+        if let Some(watcher) = &self.watcher {
+            if watcher.changed() {
+                self.tera.write().full_reload()?;
+            }
+        }
+        let context = ::tera::Context::from_serialize(context)?;
+        Ok(self.tera.read().render(name, &context)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_tera_engine() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("base.html"), "<h1>{% block title %}{% endblock %}</h1>").unwrap();
+        fs::create_dir(dir.path().join("users")).unwrap();
+        fs::write(
+            dir.path().join("users/show.html"),
+            r#"{% extends "base.html" %}{% block title %}Hello {{ name }}{% endblock %}"#,
+        )
+        .unwrap();
+        let engine = TeraEngine::from_dir(dir.path()).unwrap().hot_reload(true);
+        let mut context = Context::new();
+        context.insert("name", "salvo");
+        assert_eq!(engine.render("users/show.html", &context).unwrap(), "<h1>Hello salvo</h1>");
+
+        fs::write(dir.path().join("base.html"), "<h2>{% block title %}{% endblock %}!</h2>").unwrap();
+        assert_eq!(engine.render("users/show.html", &context).unwrap(), "<h2>Hello salvo!</h2>");
+        assert!(engine.render("missing.html", &context).is_err());
+
+        let engine = engine.hot_reload(false);
+        fs::write(dir.path().join("base.html"), "{% block title %}{% endblock %}").unwrap();
+        assert_eq!(engine.render("users/show.html", &context).unwrap(), "<h2>Hello salvo!</h2>");
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use parking_lot::Mutex;
+
+/// Hash of the paths and contents of the files in a directory.
+///
+/// Contents are hashed rather than sizes and modified times are compared, because an edit keeping the size
+/// within one tick of a coarse mtime can not be detected by metadata.
+type Stamp = u64;
+
+/// Detects changes of the files in a template directory, it is enabled by default in debug builds.
+#[derive(Debug)]
+pub(crate) struct DirWatcher {
+    dir: PathBuf,
+    stamp: Mutex<Stamp>,
+    enabled: bool,
+}
+impl DirWatcher {
+    pub(crate) fn new(dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        let stamp = Mutex::new(stamp(&dir));
+        Self {
+            dir,
+            stamp,
+            enabled: cfg!(debug_assertions),
+        }
+    }
+
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns `true` if the watcher is enabled and the directory is changed since the last call.
+    pub(crate) fn changed(&self) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let current = stamp(&self.dir);
+        let mut stamp = self.stamp.lock();
+        if *stamp != current {
+            *stamp = current;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+fn stamp(dir: &Path) -> Stamp {
+    let mut hasher = DefaultHasher::new();
+    for path in walk_files(dir) {
+        if let Ok(content) = fs::read(&path) {
+            path.hash(&mut hasher);
+            content.hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+/// List all the files in the directory and its sub directories.
+pub(crate) fn walk_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    let mut dirs = vec![dir.to_owned()];
+    while let Some(dir) = dirs.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dir_watcher() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("partials")).unwrap();
+        fs::write(dir.path().join("index.html"), "index").unwrap();
+        let mut watcher = DirWatcher::new(dir.path());
+        watcher.set_enabled(true);
+        assert!(!watcher.changed());
+
+        fs::write(dir.path().join("partials/header.html"), "header").unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+        assert_eq!(
+            walk_files(dir.path()),
+            vec![dir.path().join("index.html"), dir.path().join("partials/header.html")]
+        );
+
+        fs::write(dir.path().join("index.html"), "new index").unwrap();
+        assert!(watcher.changed());
+        // Same size and written at once, the modified time may not change.
+        fs::write(dir.path().join("index.html"), "old index").unwrap();
+        assert!(watcher.changed());
+        fs::remove_file(dir.path().join("partials/header.html")).unwrap();
+        assert!(watcher.changed());
+
+        watcher.set_enabled(false);
+        fs::write(dir.path().join("index.html"), "index").unwrap();
+        assert!(!watcher.changed());
+    }
+}